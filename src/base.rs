@@ -1,10 +1,49 @@
-use clap::Command;
+use clap::{
+    Arg,
+    Command,
+};
+use crate::ctl::backup::{
+    init_create_backup_cmd,
+    init_list_backup_cmd,
+    init_prune_backup_cmd,
+    init_restore_backup_cmd,
+};
+use crate::ctl::completion::init_completions_cmd;
+use crate::ctl::ctags::{
+    init_clean_ctags_cmd,
+    init_delete_ctags_cmd,
+    init_generate_ctags_cmd,
+    init_list_ctags_cmd,
+    init_update_ctags_cmd,
+    init_watch_ctags_cmd,
+};
 use crate::ctl::environment::{
     init_create_environment_cmd,
     init_current_environment_cmd,
+    init_deactivate_environment_cmd,
+    init_default_environment_cmd,
+    init_edit_cmd,
+    init_exec_cmd,
     init_list_environment_cmd,
+    init_local_cmd,
     init_remove_environment_cmd,
     init_switch_environment_cmd,
+    init_undelete_environment_cmd,
+    init_update_environment_cmd,
+};
+use crate::ctl::shell::{
+    init_shell_cmd,
+    init_shell_init_cmd,
+};
+use crate::ctl::shim::{
+    init_exec_shim_cmd,
+    init_install_shim_cmd,
+    init_which_shim_cmd,
+};
+use crate::ctl::template::{
+    init_extract_template_cmd,
+    init_list_template_cmd,
+    init_show_template_cmd,
 };
 
 pub struct SubCmds {
@@ -13,6 +52,25 @@ pub struct SubCmds {
     pub _switch: Command,
     pub _current: Command,
     pub _remove: Command,
+    pub _update: Command,
+    pub _show: Command,
+    pub _extract: Command,
+    pub _restore: Command,
+    pub _prune: Command,
+    pub _undelete: Command,
+    pub _default: Command,
+    pub _deactivate: Command,
+    pub _exec: Command,
+    pub _edit: Command,
+    pub _shell_init: Command,
+    pub _shell: Command,
+    pub _local: Command,
+    pub _shim: Command,
+    pub _generate: Command,
+    pub _delete: Command,
+    pub _clean: Command,
+    pub _watch: Command,
+    pub _completions: Command,
 }
 
 pub struct BaseCmd;
@@ -24,6 +82,23 @@ impl BaseCmd {
         vem_cmd = vem_cmd.subcommand_required(true);
         vem_cmd = vem_cmd.arg_required_else_help(true);
 
+        let mut output = Arg::new("output");
+        output = output
+            .help("Output format for read commands")
+            .long("output")
+            .value_name("FORMAT")
+            .value_parser(["table", "json", "toml", "template"])
+            .global(true);
+        vem_cmd = vem_cmd.arg(output);
+
+        let mut format = Arg::new("format");
+        format = format
+            .help("Per-item template for --output template, e.g. '{name}\\t{last_used}'")
+            .long("format")
+            .value_name("TEMPLATE")
+            .global(true);
+        vem_cmd = vem_cmd.arg(format);
+
         vem_cmd = init_subcmds(vem_cmd);
 
         vem_cmd
@@ -51,7 +126,7 @@ fn set_cmdcnf(mut cmd: Command, cnf: &CmdCnf) -> Command {
 fn init_create_cmd() -> Command {
     let mut create_cmd = Command::new("create");
     let create_cmdcnf = CmdCnf {
-        about: "Create a new Vim environment",
+        about: "Create a new Vim environment or backup",
         subcommand_help_heading: "RESOURCE",
         subcommand_value_name: "RESOURCE",
         subcommand_required: true,
@@ -61,13 +136,16 @@ fn init_create_cmd() -> Command {
 
     let create_environment_cmd = init_create_environment_cmd();
     create_cmd = create_cmd.subcommand(create_environment_cmd);
+
+    let create_backup_cmd = init_create_backup_cmd();
+    create_cmd = create_cmd.subcommand(create_backup_cmd);
     create_cmd
 }
 
 fn init_list_cmd() -> Command {
     let mut list_cmd = Command::new("list");
     let list_cmdcnf = CmdCnf {
-        about: "List all available environments",
+        about: "List environments, templates, backups or tag files",
         subcommand_help_heading: "RESOURCE",
         subcommand_value_name: "RESOURCE",
         subcommand_required: true,
@@ -77,6 +155,15 @@ fn init_list_cmd() -> Command {
 
     let list_environment_cmd = init_list_environment_cmd();
     list_cmd = list_cmd.subcommand(list_environment_cmd);
+
+    let list_template_cmd = init_list_template_cmd();
+    list_cmd = list_cmd.subcommand(list_template_cmd);
+
+    let list_backup_cmd = init_list_backup_cmd();
+    list_cmd = list_cmd.subcommand(list_backup_cmd);
+
+    let list_ctags_cmd = init_list_ctags_cmd();
+    list_cmd = list_cmd.subcommand(list_ctags_cmd);
     list_cmd
 }
 
@@ -115,7 +202,7 @@ fn init_current_cmd() -> Command {
 fn init_remove_cmd() -> Command {
     let mut remove_cmd = Command::new("remove");
     let remove_cmdcnf = CmdCnf {
-        about: "Remove an environment",
+        about: "Remove an environment (moved to the trash)",
         subcommand_help_heading: "RESOURCE",
         subcommand_value_name: "RESOURCE",
         subcommand_required: true,
@@ -128,6 +215,218 @@ fn init_remove_cmd() -> Command {
     remove_cmd
 }
 
+fn init_update_cmd() -> Command {
+    let mut update_cmd = Command::new("update");
+    let update_cmdcnf = CmdCnf {
+        about: "Update an environment's description or tags, or regenerate ctags",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    update_cmd = set_cmdcnf(update_cmd, &update_cmdcnf);
+
+    let update_environment_cmd = init_update_environment_cmd();
+    update_cmd = update_cmd.subcommand(update_environment_cmd);
+
+    let update_ctags_cmd = init_update_ctags_cmd();
+    update_cmd = update_cmd.subcommand(update_ctags_cmd);
+    update_cmd
+}
+
+fn init_show_cmd() -> Command {
+    let mut show_cmd = Command::new("show");
+    let show_cmdcnf = CmdCnf {
+        about: "Show details of a resource",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    show_cmd = set_cmdcnf(show_cmd, &show_cmdcnf);
+
+    let show_template_cmd = init_show_template_cmd();
+    show_cmd = show_cmd.subcommand(show_template_cmd);
+    show_cmd
+}
+
+fn init_extract_cmd() -> Command {
+    let mut extract_cmd = Command::new("extract");
+    let extract_cmdcnf = CmdCnf {
+        about: "Extract a bundled resource to disk",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    extract_cmd = set_cmdcnf(extract_cmd, &extract_cmdcnf);
+
+    let extract_template_cmd = init_extract_template_cmd();
+    extract_cmd = extract_cmd.subcommand(extract_template_cmd);
+    extract_cmd
+}
+
+fn init_restore_cmd() -> Command {
+    let mut restore_cmd = Command::new("restore");
+    let restore_cmdcnf = CmdCnf {
+        about: "Restore an environment from a backup",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    restore_cmd = set_cmdcnf(restore_cmd, &restore_cmdcnf);
+
+    let restore_backup_cmd = init_restore_backup_cmd();
+    restore_cmd = restore_cmd.subcommand(restore_backup_cmd);
+    restore_cmd
+}
+
+fn init_prune_cmd() -> Command {
+    let mut prune_cmd = Command::new("prune");
+    let prune_cmdcnf = CmdCnf {
+        about: "Delete expired backups",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    prune_cmd = set_cmdcnf(prune_cmd, &prune_cmdcnf);
+
+    let prune_backup_cmd = init_prune_backup_cmd();
+    prune_cmd = prune_cmd.subcommand(prune_backup_cmd);
+    prune_cmd
+}
+
+fn init_undelete_cmd() -> Command {
+    let mut undelete_cmd = Command::new("undelete");
+    let undelete_cmdcnf = CmdCnf {
+        about: "Restore a removed environment from the trash",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    undelete_cmd = set_cmdcnf(undelete_cmd, &undelete_cmdcnf);
+
+    let undelete_environment_cmd = init_undelete_environment_cmd();
+    undelete_cmd = undelete_cmd.subcommand(undelete_environment_cmd);
+    undelete_cmd
+}
+
+fn init_default_cmd() -> Command {
+    let mut default_cmd = Command::new("default");
+    let default_cmdcnf = CmdCnf {
+        about: "Set the environment used when none is active",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    default_cmd = set_cmdcnf(default_cmd, &default_cmdcnf);
+
+    let default_environment_cmd = init_default_environment_cmd();
+    default_cmd = default_cmd.subcommand(default_environment_cmd);
+    default_cmd
+}
+
+fn init_deactivate_cmd() -> Command {
+    let mut deactivate_cmd = Command::new("deactivate");
+    let deactivate_cmdcnf = CmdCnf {
+        about: "Deactivate the current environment and restore your own dotfiles",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    deactivate_cmd = set_cmdcnf(deactivate_cmd, &deactivate_cmdcnf);
+
+    let deactivate_environment_cmd = init_deactivate_environment_cmd();
+    deactivate_cmd = deactivate_cmd.subcommand(deactivate_environment_cmd);
+    deactivate_cmd
+}
+
+fn init_shim_cmd() -> Command {
+    let mut shim_cmd = Command::new("shim");
+    let shim_cmdcnf = CmdCnf {
+        about: "Manage vim/nvim shims that follow the active environment",
+        subcommand_help_heading: "COMMAND",
+        subcommand_value_name: "COMMAND",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    shim_cmd = set_cmdcnf(shim_cmd, &shim_cmdcnf);
+
+    shim_cmd = shim_cmd.subcommand(init_install_shim_cmd());
+    shim_cmd = shim_cmd.subcommand(init_which_shim_cmd());
+    shim_cmd = shim_cmd.subcommand(init_exec_shim_cmd());
+    shim_cmd
+}
+
+fn init_generate_cmd() -> Command {
+    let mut generate_cmd = Command::new("generate");
+    let generate_cmdcnf = CmdCnf {
+        about: "Generate tag files for an environment",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    generate_cmd = set_cmdcnf(generate_cmd, &generate_cmdcnf);
+
+    let generate_ctags_cmd = init_generate_ctags_cmd();
+    generate_cmd = generate_cmd.subcommand(generate_ctags_cmd);
+    generate_cmd
+}
+
+fn init_delete_cmd() -> Command {
+    let mut delete_cmd = Command::new("delete");
+    let delete_cmdcnf = CmdCnf {
+        about: "Delete generated tag files",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    delete_cmd = set_cmdcnf(delete_cmd, &delete_cmdcnf);
+
+    let delete_ctags_cmd = init_delete_ctags_cmd();
+    delete_cmd = delete_cmd.subcommand(delete_ctags_cmd);
+    delete_cmd
+}
+
+fn init_clean_cmd() -> Command {
+    let mut clean_cmd = Command::new("clean");
+    let clean_cmdcnf = CmdCnf {
+        about: "Remove all generated tag files of an environment",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    clean_cmd = set_cmdcnf(clean_cmd, &clean_cmdcnf);
+
+    let clean_ctags_cmd = init_clean_ctags_cmd();
+    clean_cmd = clean_cmd.subcommand(clean_ctags_cmd);
+    clean_cmd
+}
+
+fn init_watch_cmd() -> Command {
+    let mut watch_cmd = Command::new("watch");
+    let watch_cmdcnf = CmdCnf {
+        about: "Keep tag files up to date while files change",
+        subcommand_help_heading: "RESOURCE",
+        subcommand_value_name: "RESOURCE",
+        subcommand_required: true,
+        arg_required_else_help: true,
+    };
+    watch_cmd = set_cmdcnf(watch_cmd, &watch_cmdcnf);
+
+    let watch_ctags_cmd = init_watch_ctags_cmd();
+    watch_cmd = watch_cmd.subcommand(watch_ctags_cmd);
+    watch_cmd
+}
+
 fn init_subcmds(mut cmd: Command) -> Command {
     let subcmds = SubCmds {
         _create: init_create_cmd(),
@@ -135,6 +434,25 @@ fn init_subcmds(mut cmd: Command) -> Command {
         _switch: init_switch_cmd(),
         _current: init_current_cmd(),
         _remove: init_remove_cmd(),
+        _update: init_update_cmd(),
+        _show: init_show_cmd(),
+        _extract: init_extract_cmd(),
+        _restore: init_restore_cmd(),
+        _prune: init_prune_cmd(),
+        _undelete: init_undelete_cmd(),
+        _default: init_default_cmd(),
+        _deactivate: init_deactivate_cmd(),
+        _exec: init_exec_cmd(),
+        _edit: init_edit_cmd(),
+        _shell_init: init_shell_init_cmd(),
+        _shell: init_shell_cmd(),
+        _local: init_local_cmd(),
+        _shim: init_shim_cmd(),
+        _generate: init_generate_cmd(),
+        _delete: init_delete_cmd(),
+        _clean: init_clean_cmd(),
+        _watch: init_watch_cmd(),
+        _completions: init_completions_cmd(),
     };
     
     cmd = cmd.subcommand(subcmds._create);
@@ -142,5 +460,24 @@ fn init_subcmds(mut cmd: Command) -> Command {
     cmd = cmd.subcommand(subcmds._switch);
     cmd = cmd.subcommand(subcmds._current);
     cmd = cmd.subcommand(subcmds._remove);
+    cmd = cmd.subcommand(subcmds._update);
+    cmd = cmd.subcommand(subcmds._show);
+    cmd = cmd.subcommand(subcmds._extract);
+    cmd = cmd.subcommand(subcmds._restore);
+    cmd = cmd.subcommand(subcmds._prune);
+    cmd = cmd.subcommand(subcmds._undelete);
+    cmd = cmd.subcommand(subcmds._default);
+    cmd = cmd.subcommand(subcmds._deactivate);
+    cmd = cmd.subcommand(subcmds._exec);
+    cmd = cmd.subcommand(subcmds._edit);
+    cmd = cmd.subcommand(subcmds._shell_init);
+    cmd = cmd.subcommand(subcmds._shell);
+    cmd = cmd.subcommand(subcmds._local);
+    cmd = cmd.subcommand(subcmds._shim);
+    cmd = cmd.subcommand(subcmds._generate);
+    cmd = cmd.subcommand(subcmds._delete);
+    cmd = cmd.subcommand(subcmds._clean);
+    cmd = cmd.subcommand(subcmds._watch);
+    cmd = cmd.subcommand(subcmds._completions);
     cmd
 }
\ No newline at end of file
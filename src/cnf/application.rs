@@ -54,6 +54,11 @@ pub fn get_vem_home() -> PathBuf {
     }
 }
 
+// Environment chosen for this shell session by `vem shell` or `vem exec`
+pub fn session_environment() -> Option<String> {
+    std::env::var("VEM_ENV").ok().filter(|name| !name.is_empty())
+}
+
 // Get the configuration file path
 pub fn config_path() -> PathBuf {
     if let Ok(config_path) = std::env::var("VEM_CONFIG") {
@@ -68,6 +73,62 @@ pub fn current_link_path() -> PathBuf {
     get_vem_home().join("current")
 }
 
+// Get the state file naming the current environment in hard-link mode
+pub fn current_state_path() -> PathBuf {
+    get_vem_home().join("current.state")
+}
+
+// Get the root of environment backups
+pub fn backup_root() -> PathBuf {
+    get_vem_home().join("backups")
+}
+
+// Get the trash directory holding removed environments
+pub fn trash_root() -> PathBuf {
+    get_vem_home().join("trash")
+}
+
+// Get the directory holding the vim/nvim shims
+pub fn bin_root() -> PathBuf {
+    get_vem_home().join("bin")
+}
+
+// Get the directory holding the user's original dotfiles replaced by activation
+pub fn dotfiles_backup_root() -> PathBuf {
+    get_vem_home().join("dotfiles")
+}
+
+// Get the user's home directory (where ~/.vimrc and ~/.vim live)
+pub fn home_path() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+// Expand a leading `~` in a configured path to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix('~') {
+        Some("") => home_path(),
+        Some(rest) if rest.starts_with('/') || rest.starts_with('\\') => home_path().join(&rest[1..]),
+        _ => PathBuf::from(path),
+    }
+}
+
+// Get the XDG config directory (where nvim/ lives)
+pub fn xdg_config_home() -> PathBuf {
+    match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => home_path().join(".config"),
+    }
+}
+
+// Get the user template directory
+pub fn template_root() -> PathBuf {
+    if let Ok(template_dir) = std::env::var("VEM_TEMPLATE_DIR") {
+        PathBuf::from(template_dir)
+    } else {
+        get_vem_home().join("templates")
+    }
+}
+
 impl app_config {
     // Load configuration from file, creating default if not exists
     pub fn load() -> Result<Self, vem_error_t> {
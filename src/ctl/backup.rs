@@ -0,0 +1,169 @@
+use clap::{
+    Arg,
+    ArgAction,
+    ArgMatches,
+    Command,
+};
+use clap_complete::engine::ArgValueCandidates;
+use crate::ctl::completion::environment_candidates;
+use crate::ctl::environment::{format_size, report_error};
+use crate::ctl::output::{output_format, print_line, print_response, record};
+use crate::usc::backup::BackupUsecase;
+use crate::util::error::exit_code_t;
+
+pub fn init_create_backup_cmd() -> Command {
+    let mut backup = Command::new("backup");
+    backup = backup.about("Back up an environment now");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment name to back up")
+        .required(true)
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    backup = backup.arg(name);
+
+    backup
+}
+
+pub fn init_list_backup_cmd() -> Command {
+    let mut backups = Command::new("backups");
+    backups = backups.visible_alias("backup");
+    backups = backups.about("List backup resources");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Only list backups of this environment")
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    backups = backups.arg(name);
+
+    let mut verbose = Arg::new("verbose");
+    verbose = verbose
+        .help("Show verbose output")
+        .short('v')
+        .long("verbose")
+        .action(ArgAction::SetTrue);
+    backups = backups.arg(verbose);
+
+    backups
+}
+
+pub fn init_restore_backup_cmd() -> Command {
+    let mut backup = Command::new("backup");
+    backup = backup.about("Restore an environment from a backup");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment name to restore")
+        .required(true)
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    backup = backup.arg(name);
+
+    let mut id = Arg::new("id");
+    id = id
+        .help("Backup id from `vem list backups` (defaults to the latest)")
+        .value_name("ID");
+    backup = backup.arg(id);
+
+    backup
+}
+
+pub fn init_prune_backup_cmd() -> Command {
+    let mut backups = Command::new("backups");
+    backups = backups.visible_alias("backup");
+    backups = backups.about("Delete backups older than the retention window");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Only prune backups of this environment")
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    backups = backups.arg(name);
+
+    let mut days = Arg::new("days");
+    days = days
+        .help("Retention window in days (defaults to backup_retention_days; 0 keeps everything)")
+        .long("days")
+        .value_name("DAYS")
+        .value_parser(clap::value_parser!(u32));
+    backups = backups.arg(days);
+
+    backups
+}
+
+pub fn create_backup(usecase: &dyn BackupUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+
+    match usecase.create(&name) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn list_backup(usecase: &dyn BackupUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").map(String::as_str);
+    let verbose = matches.get_flag("verbose");
+    let output = match output_format(matches) {
+        Ok(output) => output,
+        Err(err) => return report_error(&err),
+    };
+
+    match usecase.list(name) {
+        Ok(response) => {
+            let items: Vec<_> = response.backups.iter().map(|backup| record(backup, &[])).collect();
+            print_response(&output, &response, &items, || {
+                if response.backups.is_empty() {
+                    print_line!("{}", response.messages);
+                }
+                for backup in &response.backups {
+                    if verbose {
+                        print_line!(
+                            "{}\t{}\t{}\t{}",
+                            backup.environment,
+                            backup.id,
+                            format_size(backup.size),
+                            backup.path.display()
+                        );
+                    } else {
+                        print_line!("{}\t{}", backup.environment, backup.id);
+                    }
+                }
+            })
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn restore_backup(usecase: &dyn BackupUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+    let id = matches.get_one::<String>("id").map(String::as_str);
+
+    match usecase.restore(&name, id) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn prune_backup(usecase: &dyn BackupUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").map(String::as_str);
+    let days = matches.get_one::<u32>("days").copied();
+
+    match usecase.prune(name, days) {
+        Ok(response) => {
+            for backup in &response.backups {
+                println!("{}\t{}", backup.environment, backup.id);
+            }
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
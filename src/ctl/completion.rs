@@ -0,0 +1,79 @@
+use std::io::Write;
+
+use clap::{
+    Arg,
+    ArgMatches,
+    Command,
+};
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+use crate::cnf::application::app_config;
+use crate::ctl::environment::report_error;
+use crate::rep::environment::{self as environment_rep, EnvironmentRepository};
+use crate::rep::template::{self as template_rep, TemplateRepository};
+use crate::util::error::{exit_code_t, vem_error_t};
+
+// Environment variable through which the shell asks vem for candidates
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+pub fn init_completions_cmd() -> Command {
+    let mut completions = Command::new("completions");
+    completions = completions.about("Print the shell completion script");
+    completions = completions.arg_required_else_help(true);
+
+    let mut shell = Arg::new("shell");
+    shell = shell
+        .help("Shell to generate completions for")
+        .required(true)
+        .value_name("SHELL")
+        .value_parser(["bash", "zsh", "fish"]);
+    completions = completions.arg(shell);
+
+    completions
+}
+
+/// Print the registration script; the shell then calls back into vem for each completion
+pub fn completions(matches: &ArgMatches) -> exit_code_t {
+    let shell = matches.get_one::<String>("shell").cloned().unwrap_or_default();
+    let shells = Shells::builtins();
+    let Some(completer) = shells.completer(&shell) else {
+        return exit_code_t::INVALID_ARGUMENTS;
+    };
+
+    let result = std::env::current_exe().and_then(|vem| {
+        let mut script = Vec::new();
+        completer.write_registration(COMPLETE_VAR, "vem", "vem", &vem.display().to_string(), &mut script)?;
+        std::io::stdout().lock().write_all(&script)
+    });
+    match result {
+        Ok(()) => exit_code_t::SUCCESS,
+        Err(err) => report_error(&vem_error_t::FileSystemError(err)),
+    }
+}
+
+/// Candidates for environment NAME arguments; nothing when the config cannot be read
+pub fn environment_candidates() -> Vec<CompletionCandidate> {
+    let Ok(config) = app_config::load() else {
+        return Vec::new();
+    };
+    environment_rep::new(config)
+        .list()
+        .unwrap_or_default()
+        .0
+        .into_iter()
+        .map(|env| CompletionCandidate::new(env.name).help(env.description.map(Into::into)))
+        .collect()
+}
+
+/// Candidates for template NAME arguments
+pub fn template_candidates() -> Vec<CompletionCandidate> {
+    template_rep::new()
+        .list()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|template| {
+            let description = template.manifest.environment.description.clone();
+            CompletionCandidate::new(template.name).help(description.map(Into::into))
+        })
+        .collect()
+}
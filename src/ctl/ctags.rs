@@ -0,0 +1,371 @@
+use std::time::Duration;
+
+use clap::{
+    Arg,
+    ArgAction,
+    ArgGroup,
+    ArgMatches,
+    Command,
+};
+use clap_complete::engine::ArgValueCandidates;
+use crate::ctl::completion::environment_candidates;
+use crate::ctl::environment::{confirm, format_size, report_error, report_warnings};
+use crate::ctl::output::{output_format, print_line, print_response, print_table, record, strings};
+use crate::ent::model::ctags::{CTAG, ctag_status_t};
+use crate::ent::request::ctags::CTAGS_GENERATE as RequestCtagsGenerate;
+use crate::ent::response::ctags::CTAGS as ResponseCtags;
+use crate::usc::ctags::CtagsUsecase;
+use crate::util::error::{exit_code_t, vem_error_t};
+use crate::util::mcode::{self, format_message, log_level_t};
+
+pub fn init_generate_ctags_cmd() -> Command {
+    let mut ctags = Command::new("ctags");
+    ctags = ctags.about("Generate tags for a repository or tag set defined in vem.toml");
+
+    let mut repository = Arg::new("repository");
+    repository = repository
+        .help("Repository name from [ctags.repositories]")
+        .value_name("REPOSITORY");
+    ctags = ctags.arg(repository);
+
+    let mut tag_set = Arg::new("tag_set");
+    tag_set = tag_set
+        .help("Generate the merged tag file of a [ctags.tags] set instead")
+        .long("tag-set")
+        .value_name("NAME");
+    ctags = ctags.arg(tag_set);
+
+    let mut target = ArgGroup::new("target");
+    target = target
+        .args(["repository", "tag_set"])
+        .required(true);
+    ctags = ctags.group(target);
+
+    ctags = add_run_args(ctags);
+    ctags
+}
+
+pub fn init_update_ctags_cmd() -> Command {
+    let mut ctags = Command::new("ctags");
+    ctags = ctags.about("Update tags for a repository, or for every enabled one, re-tagging changed files");
+
+    let mut repository = Arg::new("repository");
+    repository = repository
+        .help("Repository name from [ctags.repositories]")
+        .value_name("REPOSITORY");
+    ctags = ctags.arg(repository);
+
+    let mut all = Arg::new("all");
+    all = all
+        .help("Update every enabled repository")
+        .long("all")
+        .action(ArgAction::SetTrue);
+    ctags = ctags.arg(all);
+
+    let mut target = ArgGroup::new("target");
+    target = target
+        .args(["repository", "all"])
+        .required(true);
+    ctags = ctags.group(target);
+
+    let mut incremental = Arg::new("incremental");
+    incremental = incremental
+        .help("Only re-tag files changed since the last run (the default)")
+        .long("incremental")
+        .action(ArgAction::SetTrue);
+    ctags = ctags.arg(incremental);
+
+    let mut force = Arg::new("force");
+    force = force
+        .help("Re-tag every file instead of only the changed ones")
+        .long("force")
+        .action(ArgAction::SetTrue)
+        .conflicts_with("incremental");
+    ctags = ctags.arg(force);
+
+    ctags = add_run_args(ctags);
+    ctags
+}
+
+pub fn init_delete_ctags_cmd() -> Command {
+    let mut ctags = Command::new("ctags");
+    ctags = ctags.about("Delete the tag file of a repository");
+
+    let mut repository = Arg::new("repository");
+    repository = repository
+        .help("Repository name whose tags to delete")
+        .required(true)
+        .value_name("REPOSITORY");
+    ctags = ctags.arg(repository);
+
+    let mut tag_file = Arg::new("tag_file");
+    tag_file = tag_file
+        .help("Delete this tag file from the environment's ctags directory instead")
+        .long("tag-file")
+        .value_name("FILE");
+    ctags = ctags.arg(tag_file);
+
+    ctags = ctags.arg(env_arg());
+    ctags
+}
+
+pub fn init_list_ctags_cmd() -> Command {
+    let mut ctags = Command::new("ctags");
+    ctags = ctags.about("List tag files with the columns from [ctags.commands] list_fields");
+
+    let mut repository = Arg::new("repository");
+    repository = repository
+        .help("Only list tags of this repository")
+        .long("repository")
+        .value_name("REPOSITORY");
+    ctags = ctags.arg(repository);
+
+    ctags = ctags.arg(env_arg());
+    ctags
+}
+
+pub fn init_clean_ctags_cmd() -> Command {
+    let mut ctags = Command::new("ctags");
+    ctags = ctags.about("Remove every tag file of the environment");
+
+    let mut no_backup = Arg::new("no_backup");
+    no_backup = no_backup
+        .help("Skip the environment backup taken when clean_backup is set")
+        .long("no-backup")
+        .action(ArgAction::SetTrue);
+    ctags = ctags.arg(no_backup);
+
+    let mut no_confirm = Arg::new("no_confirm");
+    no_confirm = no_confirm
+        .help("Skip the confirmation prompt")
+        .long("no-confirm")
+        .action(ArgAction::SetTrue);
+    ctags = ctags.arg(no_confirm);
+
+    ctags = ctags.arg(env_arg());
+    ctags
+}
+
+pub fn init_watch_ctags_cmd() -> Command {
+    let mut ctags = Command::new("ctags");
+    ctags = ctags.about("Watch repository paths and update their tags after changes (runs until interrupted)");
+
+    let mut repository = Arg::new("repository");
+    repository = repository
+        .help("Repository name from [ctags.repositories]")
+        .value_name("REPOSITORY");
+    ctags = ctags.arg(repository);
+
+    let mut all = Arg::new("all");
+    all = all
+        .help("Watch every enabled repository")
+        .long("all")
+        .action(ArgAction::SetTrue);
+    ctags = ctags.arg(all);
+
+    let mut target = ArgGroup::new("target");
+    target = target
+        .args(["repository", "all"])
+        .required(true);
+    ctags = ctags.group(target);
+
+    let mut debounce = Arg::new("debounce");
+    debounce = debounce
+        .help("Quiet time after the last change before updating, in milliseconds")
+        .long("debounce")
+        .value_name("MS")
+        .value_parser(clap::value_parser!(u64))
+        .default_value("500");
+    ctags = ctags.arg(debounce);
+
+    ctags = add_run_args(ctags);
+    ctags
+}
+
+// -e/--env, shared by every ctags command
+fn env_arg() -> Arg {
+    let mut env = Arg::new("env");
+    env = env
+        .help("Environment to use instead of the current one")
+        .short('e')
+        .long("env")
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    env
+}
+
+// Options shared by generate and update
+fn add_run_args(mut ctags: Command) -> Command {
+    let mut languages = Arg::new("languages");
+    languages = languages
+        .help("Only tag these ctags languages (comma-separated)")
+        .long("languages")
+        .value_name("LANGS")
+        .value_delimiter(',')
+        .action(ArgAction::Append);
+    ctags = ctags.arg(languages);
+
+    let mut exclude = Arg::new("exclude");
+    exclude = exclude
+        .help("Exclude pattern, added to [ctags.global] exclude_patterns (repeatable)")
+        .long("exclude")
+        .value_name("PATTERN")
+        .action(ArgAction::Append);
+    ctags = ctags.arg(exclude);
+
+    ctags = ctags.arg(env_arg());
+    ctags
+}
+
+fn generate_request(matches: &ArgMatches) -> RequestCtagsGenerate {
+    RequestCtagsGenerate {
+        environment: matches.get_one::<String>("env").cloned(),
+        repository: matches.get_one::<String>("repository").cloned(),
+        tag_set: matches.try_get_one::<String>("tag_set").ok().flatten().cloned(),
+        languages: strings(matches, "languages"),
+        exclude: strings(matches, "exclude"),
+        force: matches.try_get_one::<bool>("force").ok().flatten().copied().unwrap_or(false),
+    }
+}
+
+pub fn generate_ctags(usecase: &dyn CtagsUsecase, matches: &ArgMatches) -> exit_code_t {
+    match usecase.generate(generate_request(matches)) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn update_ctags(usecase: &dyn CtagsUsecase, matches: &ArgMatches) -> exit_code_t {
+    match usecase.update(generate_request(matches)) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            for tag in &response.tags {
+                println!("{}\t{}\t{}", tag.name, format_size(tag.size), tag.tag_file.display());
+            }
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn delete_ctags(usecase: &dyn CtagsUsecase, matches: &ArgMatches) -> exit_code_t {
+    let environment = matches.get_one::<String>("env").map(String::as_str);
+    let repository = matches.get_one::<String>("repository").cloned().unwrap_or_default();
+    let tag_file = matches.get_one::<String>("tag_file").map(String::as_str);
+
+    match usecase.delete(environment, &repository, tag_file) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn list_ctags(usecase: &dyn CtagsUsecase, matches: &ArgMatches) -> exit_code_t {
+    let environment = matches.get_one::<String>("env").map(String::as_str);
+    let repository = matches.get_one::<String>("repository").map(String::as_str);
+    let output = match output_format(matches) {
+        Ok(output) => output,
+        Err(err) => return report_error(&err),
+    };
+
+    match usecase.list(environment, repository) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            let items: Vec<_> = response.tags.iter().map(|tag| record(tag, &[])).collect();
+            print_response(&output, &response, &items, || {
+                if response.tags.is_empty() {
+                    print_line!("{}", response.messages);
+                    return;
+                }
+                let header = response.fields.iter().map(|field| field.replace('_', " ").to_uppercase()).collect();
+                let mut rows: Vec<Vec<String>> = vec![header];
+                for tag in &response.tags {
+                    rows.push(response.fields.iter().map(|field| ctag_field(tag, field)).collect());
+                }
+                print_table(&rows, |_| "");
+            })
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn clean_ctags(usecase: &dyn CtagsUsecase, matches: &ArgMatches) -> exit_code_t {
+    let environment = matches.get_one::<String>("env").map(String::as_str);
+
+    let confirm_clean = match usecase.clean_confirm(environment) {
+        Ok(confirm_clean) => confirm_clean && !matches.get_flag("no_confirm"),
+        Err(err) => return report_error(&err),
+    };
+    if confirm_clean {
+        let listed = match usecase.list(environment, None) {
+            Ok(response) => response,
+            Err(err) => return report_error(&err),
+        };
+        if !listed.tags.is_empty() {
+            let question = format!(
+                "Remove {} tag file(s) from environment '{}'? [y/N] ",
+                listed.tags.len(),
+                listed.environment
+            );
+            if !confirm(&question) {
+                eprintln!("{}", format_message(log_level_t::NOTICE, "VCC_CANCEL", mcode::VCC_CANCEL));
+                return exit_code_t::GENERAL_ERROR;
+            }
+        }
+    }
+
+    match usecase.clean(environment, !matches.get_flag("no_backup")) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn watch_ctags(usecase: &dyn CtagsUsecase, matches: &ArgMatches) -> exit_code_t {
+    let debounce = Duration::from_millis(matches.get_one::<u64>("debounce").copied().unwrap_or(500));
+
+    // One timestamped line per update; a failed update is logged and watching goes on
+    let mut report = |result: Result<ResponseCtags, vem_error_t>| {
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+        match result {
+            Ok(response) => {
+                report_warnings(&response.warnings);
+                println!("{} {}", now, format_message(log_level_t::INFO, &response.mcode, &response.messages));
+            },
+            Err(err) => eprintln!("{} {}", now, format_message(log_level_t::ERROR, "VCTW2", &err.to_string())),
+        }
+    };
+    match usecase.watch(generate_request(matches), debounce, &mut report) {
+        Ok(()) => exit_code_t::SUCCESS,
+        Err(err) => report_error(&err),
+    }
+}
+
+// Table cell for one of the list_fields columns
+fn ctag_field(tag: &CTAG, field: &str) -> String {
+    match field {
+        "name" => tag.name.clone(),
+        "repository" => tag.repository.clone(),
+        "tag_file" => tag.tag_file.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+        "last_updated" => tag.last_updated.format("%Y-%m-%d %H:%M").to_string(),
+        "size" => format_size(tag.size),
+        "status" => match tag.status {
+            ctag_status_t::ACTIVE => "active",
+            ctag_status_t::DISABLED => "disabled",
+            ctag_status_t::ORPHANED => "orphaned",
+        }
+        .to_string(),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,116 @@
+use clap::ArgMatches;
+use crate::cnf::application::app_config;
+use crate::ctl::backup::{
+    create_backup,
+    list_backup,
+    prune_backup,
+    restore_backup,
+};
+use crate::ctl::completion::completions;
+use crate::ctl::ctags::{
+    clean_ctags,
+    delete_ctags,
+    generate_ctags,
+    list_ctags,
+    update_ctags,
+    watch_ctags,
+};
+use crate::ctl::environment::{
+    create_environment,
+    current_environment,
+    deactivate_environment,
+    default_environment,
+    edit_environment,
+    exec_environment,
+    list_environment,
+    local_environment,
+    remove_environment,
+    report_error,
+    switch_environment,
+    undelete_environment,
+    update_environment,
+};
+use crate::ctl::shell::{
+    shell_environment,
+    shell_init,
+};
+use crate::ctl::shim::{
+    exec_shim,
+    install_shim,
+    which_shim,
+};
+use crate::ctl::template::{
+    extract_template,
+    list_template,
+    show_template,
+};
+use crate::usc::backup as backup_usc;
+use crate::usc::ctags as ctags_usc;
+use crate::usc::environment as environment_usc;
+use crate::usc::shim as shim_usc;
+use crate::usc::template as template_usc;
+use crate::util::error::exit_code_t;
+
+/// Route parsed command-line matches to the matching resource handler
+pub fn dispatch(matches: &ArgMatches) -> exit_code_t {
+    let Some((verb, sub)) = matches.subcommand() else {
+        return exit_code_t::INVALID_ARGUMENTS;
+    };
+
+    // Shell setup has to work before there is a usable config.toml (and so does the
+    // completion callback, answered in main before dispatch)
+    match verb {
+        "shell-init" => return shell_init(sub),
+        "completions" => return completions(sub),
+        _ => {},
+    }
+
+    let config = match app_config::load() {
+        Ok(config) => config,
+        Err(err) => return report_error(&err),
+    };
+    if let Err(err) = config.validate() {
+        return report_error(&err);
+    }
+
+    // Verbs that act on an environment directly, without a resource
+    match verb {
+        "exec" => return exec_environment(&environment_usc::new(config), sub),
+        "edit" => return edit_environment(&environment_usc::new(config), sub),
+        "shell" => return shell_environment(&environment_usc::new(config), sub),
+        "local" => return local_environment(&environment_usc::new(config), sub),
+        _ => {},
+    }
+    let Some((resource, args)) = sub.subcommand() else {
+        return exit_code_t::INVALID_ARGUMENTS;
+    };
+
+    match (verb, resource) {
+        ("create", "env") => create_environment(&environment_usc::new(config), args),
+        ("list", "envs") => list_environment(&environment_usc::new(config), args),
+        ("switch", "env") => switch_environment(&environment_usc::new(config), args),
+        ("current", "env") => current_environment(&environment_usc::new(config), args),
+        ("remove", "env") => remove_environment(&environment_usc::new(config), args),
+        ("default", "env") => default_environment(&environment_usc::new(config), args),
+        ("deactivate", "env") => deactivate_environment(&environment_usc::new(config), args),
+        ("undelete", "env") => undelete_environment(&environment_usc::new(config), args),
+        ("update", "env") => update_environment(&environment_usc::new(config), args),
+        ("list", "templates") => list_template(&template_usc::new(), args),
+        ("show", "template") => show_template(&template_usc::new(), args),
+        ("extract", "template") => extract_template(&template_usc::new(), args),
+        ("create", "backup") => create_backup(&backup_usc::new(config), args),
+        ("list", "backups") => list_backup(&backup_usc::new(config), args),
+        ("restore", "backup") => restore_backup(&backup_usc::new(config), args),
+        ("prune", "backups") => prune_backup(&backup_usc::new(config), args),
+        ("generate", "ctags") => generate_ctags(&ctags_usc::new(config), args),
+        ("update", "ctags") => update_ctags(&ctags_usc::new(config), args),
+        ("delete", "ctags") => delete_ctags(&ctags_usc::new(config), args),
+        ("list", "ctags") => list_ctags(&ctags_usc::new(config), args),
+        ("clean", "ctags") => clean_ctags(&ctags_usc::new(config), args),
+        ("watch", "ctags") => watch_ctags(&ctags_usc::new(config), args),
+        ("shim", "install") => install_shim(&shim_usc::new(config), args),
+        ("shim", "which") => which_shim(&shim_usc::new(config), args),
+        ("shim", "exec") => exec_shim(&shim_usc::new(config), args),
+        _ => exit_code_t::INVALID_ARGUMENTS,
+    }
+}
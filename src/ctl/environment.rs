@@ -1,8 +1,24 @@
+use std::io::Write;
+
 use clap::{
     Arg,
     ArgAction,
+    ArgGroup,
+    ArgMatches,
     Command,
 };
+use clap_complete::engine::ArgValueCandidates;
+use crate::ctl::completion::{environment_candidates, template_candidates};
+use crate::ent::model::manifest::editor_type_t;
+use crate::ent::request::environment::ENVIRONMENT as RequestEnvironment;
+use crate::ent::request::environment::ENVIRONMENT_LIST as RequestEnvironmentList;
+use crate::ent::request::environment::ENVIRONMENT_UPDATE as RequestEnvironmentUpdate;
+use crate::ent::request::environment::environment_sort_t;
+use crate::ent::response::environment::ENVIRONMENTS as ResponseEnvironments;
+use crate::ctl::output::{output_format, output_t, print_line, print_response, print_table, record, strings};
+use crate::usc::environment::EnvironmentUsecase;
+use crate::util::error::{exit_code_t, vem_error_t};
+use crate::util::mcode::{self, format_message, log_level_t};
 
 pub fn init_create_environment_cmd() -> Command {
     let mut env = Command::new("env");
@@ -24,6 +40,86 @@ pub fn init_create_environment_cmd() -> Command {
         .value_name("TEXT");
     env = env.arg(description);
 
+    let mut from = Arg::new("from");
+    from = from
+        .help("Template to create the environment from (e.g. basic-vim, modern-nvim)")
+        .long("from")
+        .value_name("TEMPLATE")
+        .add(ArgValueCandidates::new(template_candidates));
+    env = env.arg(from);
+
+    let mut editor = Arg::new("editor");
+    editor = editor
+        .help("Editor layout to scaffold without a template")
+        .long("editor")
+        .value_name("EDITOR")
+        .value_parser(["vim", "neovim"])
+        .conflicts_with("from");
+    env = env.arg(editor);
+
+    let mut tag = Arg::new("tag");
+    tag = tag
+        .help("Tag to attach (repeatable)")
+        .short('t')
+        .long("tag")
+        .value_name("TAG")
+        .action(ArgAction::Append);
+    env = env.arg(tag);
+
+    env
+}
+
+pub fn init_update_environment_cmd() -> Command {
+    let mut env = Command::new("env");
+    env = env.visible_alias("environment");
+    env = env.about("Update an environment resource");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment name to update")
+        .required(true)
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    env = env.arg(name);
+
+    let mut description = Arg::new("description");
+    description = description
+        .help("New description (an empty string clears it)")
+        .short('d')
+        .long("description")
+        .value_name("TEXT");
+    env = env.arg(description);
+
+    let mut add_tag = Arg::new("add_tag");
+    add_tag = add_tag
+        .help("Tag to add (repeatable)")
+        .long("add-tag")
+        .value_name("TAG")
+        .action(ArgAction::Append);
+    env = env.arg(add_tag);
+
+    let mut remove_tag = Arg::new("remove_tag");
+    remove_tag = remove_tag
+        .help("Tag to remove (repeatable)")
+        .long("remove-tag")
+        .value_name("TAG")
+        .action(ArgAction::Append);
+    env = env.arg(remove_tag);
+
+    let mut clear_tags = Arg::new("clear_tags");
+    clear_tags = clear_tags
+        .help("Remove all tags before applying --add-tag")
+        .long("clear-tags")
+        .action(ArgAction::SetTrue);
+    env = env.arg(clear_tags);
+
+    let mut changes = ArgGroup::new("changes");
+    changes = changes
+        .args(["description", "add_tag", "remove_tag", "clear_tags"])
+        .multiple(true)
+        .required(true);
+    env = env.group(changes);
+
     env
 }
 
@@ -40,6 +136,39 @@ pub fn init_list_environment_cmd() -> Command {
         .action(ArgAction::SetTrue);
     envs = envs.arg(verbose);
 
+    let mut tag = Arg::new("tag");
+    tag = tag
+        .help("Only show environments carrying this tag (repeatable, all must match)")
+        .short('t')
+        .long("tag")
+        .value_name("TAG")
+        .action(ArgAction::Append);
+    envs = envs.arg(tag);
+
+    let mut sort = Arg::new("sort");
+    sort = sort
+        .help("Sort key; timestamps sort most recent first")
+        .long("sort")
+        .value_name("KEY")
+        .value_parser(["name", "created", "updated", "last-used"])
+        .default_value("name");
+    envs = envs.arg(sort);
+
+    let mut reverse = Arg::new("reverse");
+    reverse = reverse
+        .help("Reverse the sort order")
+        .short('r')
+        .long("reverse")
+        .action(ArgAction::SetTrue);
+    envs = envs.arg(reverse);
+
+    let mut current_first = Arg::new("current_first");
+    current_first = current_first
+        .help("Show the current environment first")
+        .long("current-first")
+        .action(ArgAction::SetTrue);
+    envs = envs.arg(current_first);
+
     envs
 }
 
@@ -52,7 +181,8 @@ pub fn init_switch_environment_cmd() -> Command {
     name = name
         .help("Environment name to switch to")
         .required(true)
-        .value_name("NAME");
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
     env = env.arg(name);
 
     env
@@ -62,20 +192,548 @@ pub fn init_current_environment_cmd() -> Command {
     let mut env = Command::new("env");
     env = env.visible_alias("environment");
     env = env.about("Show current environment resource");
+
+    let mut explain = Arg::new("explain");
+    explain = explain
+        .help("Show every source the current environment can come from and which one won")
+        .long("explain")
+        .action(ArgAction::SetTrue);
+    env = env.arg(explain);
+
     env
 }
 
 pub fn init_remove_environment_cmd() -> Command {
     let mut env = Command::new("env");
     env = env.visible_alias("environment");
-    env = env.about("Remove an environment resource");
+    env = env.about("Move an environment resource to the trash");
 
     let mut name = Arg::new("name");
     name = name
         .help("Environment name to remove")
         .required(true)
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    env = env.arg(name);
+
+    let mut force = Arg::new("force");
+    force = force
+        .help("Skip the confirmation prompt")
+        .short('f')
+        .long("force")
+        .visible_alias("yes")
+        .visible_short_alias('y')
+        .action(ArgAction::SetTrue);
+    env = env.arg(force);
+
+    let mut backup = Arg::new("backup");
+    backup = backup
+        .help("Back up the environment first, even when backup_enabled is off")
+        .long("backup")
+        .action(ArgAction::SetTrue);
+    env = env.arg(backup);
+
+    env
+}
+
+pub fn init_default_environment_cmd() -> Command {
+    let mut env = Command::new("env");
+    env = env.visible_alias("environment");
+    env = env.about("Set the default environment resource");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment to use when none is active")
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    env = env.arg(name);
+
+    let mut unset = Arg::new("unset");
+    unset = unset
+        .help("Clear the default environment")
+        .long("unset")
+        .action(ArgAction::SetTrue);
+    env = env.arg(unset);
+
+    let mut target = ArgGroup::new("target");
+    target = target
+        .args(["name", "unset"])
+        .required(true);
+    env = env.group(target);
+
+    env
+}
+
+pub fn init_exec_cmd() -> Command {
+    let mut exec = Command::new("exec");
+    exec = exec.about("Run a command inside an environment without switching to it");
+    exec = exec.arg_required_else_help(true);
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment name")
+        .required(true)
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    exec = exec.arg(name);
+
+    let mut command = Arg::new("command");
+    command = command
+        .help("Command to run, e.g. -- vim file.rs")
+        .required(true)
+        .value_name("COMMAND")
+        .num_args(1..)
+        .trailing_var_arg(true)
+        .allow_hyphen_values(true);
+    exec = exec.arg(command);
+
+    exec
+}
+
+pub fn init_edit_cmd() -> Command {
+    let mut edit = Command::new("edit");
+    edit = edit.about("Open the configured editor in the current environment");
+
+    let mut env = Arg::new("env");
+    env = env
+        .help("Environment to use instead of the current one")
+        .short('e')
+        .long("env")
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    edit = edit.arg(env);
+
+    let mut files = Arg::new("files");
+    files = files
+        .help("Files to open")
+        .value_name("FILE")
+        .num_args(0..)
+        .trailing_var_arg(true)
+        .allow_hyphen_values(true);
+    edit = edit.arg(files);
+
+    edit
+}
+
+pub fn init_local_cmd() -> Command {
+    let mut local = Command::new("local");
+    local = local.about("Pin this directory tree to an environment with a .vem-env file");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment to use in this directory and below")
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    local = local.arg(name);
+
+    let mut unset = Arg::new("unset");
+    unset = unset
+        .help("Remove the .vem-env in this directory")
+        .long("unset")
+        .action(ArgAction::SetTrue)
+        .conflicts_with("name");
+    local = local.arg(unset);
+
+    local
+}
+
+pub fn init_deactivate_environment_cmd() -> Command {
+    let mut env = Command::new("env");
+    env = env.visible_alias("environment");
+    env = env.about("Deactivate the current environment resource");
+
+    env
+}
+
+pub fn init_undelete_environment_cmd() -> Command {
+    let mut env = Command::new("env");
+    env = env.visible_alias("environment");
+    env = env.about("Restore an environment resource from the trash");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment name to restore")
+        .required(true)
         .value_name("NAME");
     env = env.arg(name);
 
     env
 }
+
+pub fn create_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let request = RequestEnvironment {
+        name: matches.get_one::<String>("name").cloned().unwrap_or_default(),
+        description: matches.get_one::<String>("description").cloned(),
+        tags: strings(matches, "tag"),
+        template: matches.get_one::<String>("from").cloned(),
+        editor: match matches.get_one::<String>("editor").map(String::as_str) {
+            Some("neovim") => Some(editor_type_t::NEOVIM),
+            Some(_) => Some(editor_type_t::VIM),
+            None => None,
+        },
+    };
+
+    match usecase.create(request) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn update_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let request = RequestEnvironmentUpdate {
+        name: matches.get_one::<String>("name").cloned().unwrap_or_default(),
+        description: matches.get_one::<String>("description").cloned(),
+        add_tags: strings(matches, "add_tag"),
+        remove_tags: strings(matches, "remove_tag"),
+        clear_tags: matches.get_flag("clear_tags"),
+    };
+
+    match usecase.update(request) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn list_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let verbose = matches.get_flag("verbose");
+    let output = match output_format(matches) {
+        Ok(output) => output,
+        Err(err) => return report_error(&err),
+    };
+    let sort = match matches.get_one::<String>("sort").map(String::as_str) {
+        Some("created") => environment_sort_t::CREATED,
+        Some("updated") => environment_sort_t::UPDATED,
+        Some("last-used") => environment_sort_t::LAST_USED,
+        _ => environment_sort_t::NAME,
+    };
+    let request = RequestEnvironmentList {
+        tags: strings(matches, "tag"),
+        sort,
+        reverse: matches.get_flag("reverse"),
+        current_first: matches.get_flag("current_first"),
+        with_size: verbose || matches!(&output, output_t::TEMPLATE(format) if format.contains("{size}")),
+    };
+
+    match usecase.list(request) {
+        Ok(response) => {
+            for warning in &response.warnings {
+                eprintln!("{}", format_message(log_level_t::WARN, "VEL4", warning));
+            }
+            let items: Vec<_> = response
+                .environments
+                .iter()
+                .map(|env| {
+                    let current = response.current.as_deref() == Some(env.name.as_str());
+                    let size = response.sizes.get(&env.name).copied();
+                    record(env, &[("current", current.into()), ("size", size.into())])
+                })
+                .collect();
+            print_response(&output, &response, &items, || {
+                if response.environments.is_empty() {
+                    print_line!("{}", response.messages);
+                } else if verbose {
+                    print_environment_table(&response);
+                } else {
+                    for environment in &response.environments {
+                        print_line!("{} {}", current_marker(&response, &environment.name), environment.name);
+                    }
+                }
+            })
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+// "*" for the active environment, blank otherwise
+fn current_marker(response: &ResponseEnvironments, name: &str) -> &'static str {
+    if response.current.as_deref() == Some(name) { "*" } else { " " }
+}
+
+// Aligned columns for `list envs --verbose`
+fn print_environment_table(response: &ResponseEnvironments) {
+    let header = ["NAME", "DESCRIPTION", "TAGS", "CREATED", "LAST USED", "SIZE"].map(String::from);
+    let mut rows = vec![header];
+    for env in &response.environments {
+        rows.push([
+            env.name.clone(),
+            env.description.clone().unwrap_or_default(),
+            env.tags.join(","),
+            env.created.format("%Y-%m-%d %H:%M").to_string(),
+            env.last_used.map(|t| t.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_else(|| "never".to_string()),
+            response.sizes.get(&env.name).map(|size| format_size(*size)).unwrap_or_default(),
+        ]);
+    }
+
+    print_table(&rows, |index| if index == 0 { " " } else { current_marker(response, &rows[index][0]) });
+}
+
+// Human-readable byte count (1024-based)
+pub fn format_size(bytes: u64) -> String {
+    let units = ["B", "K", "M", "G", "T"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, units[0])
+    } else {
+        format!("{:.1}{}", size, units[unit])
+    }
+}
+
+pub fn switch_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+
+    match usecase.set_current(&name) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn current_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let output = match output_format(matches) {
+        Ok(output) => output,
+        Err(err) => return report_error(&err),
+    };
+
+    if matches.get_flag("explain") {
+        return explain_current_environment(usecase, &output);
+    }
+
+    // The one place auto_switch acts, so it never happens behind a read-only command
+    match usecase.auto_switch() {
+        Ok(Some(response)) => {
+            report_warnings(&response.warnings);
+            eprintln!("{}", format_message(log_level_t::NOTICE, &response.mcode, &response.messages));
+        },
+        Ok(None) => {},
+        Err(err) => return report_error(&err),
+    }
+
+    match usecase.get_current() {
+        Ok(response) => {
+            let items = [record(&response.environment, &[])];
+            print_response(&output, &response, &items, || {
+                // Say on stderr when the name came from default_environment rather than `current`
+                if response.mcode != "VECU1" {
+                    eprintln!("{}", format_message(log_level_t::NOTICE, &response.mcode, &response.messages));
+                }
+                print_line!("{}", response.environment.name);
+            })
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+fn explain_current_environment(usecase: &dyn EnvironmentUsecase, output: &output_t) -> exit_code_t {
+    match usecase.explain_current() {
+        Ok(response) => {
+            let items: Vec<_> = response.sources.iter().map(|source| record(source, &[])).collect();
+            print_response(output, &response, &items, || {
+                let header = ["SOURCE", "ENVIRONMENT", "DETAIL"].map(String::from);
+                let mut rows = vec![header];
+                for source in &response.sources {
+                    rows.push([
+                        source.source.clone(),
+                        source.environment.clone().unwrap_or_else(|| "-".to_string()),
+                        source.detail.clone().unwrap_or_default(),
+                    ]);
+                }
+                print_table(&rows, |index| {
+                    if index > 0 && response.sources[index - 1].selected { "*" } else { " " }
+                });
+                print_line!();
+                print_line!("{}", response.messages);
+            })
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn remove_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+
+    if !matches.get_flag("force") {
+        // Fail on a missing or in-use environment before asking about it
+        if let Err(err) = usecase.check_delete(&name) {
+            return report_error(&err);
+        }
+        let question = format!("Move environment '{}' to the trash? [y/N] ", name);
+        if !confirm(&question) {
+            eprintln!("{}", format_message(log_level_t::NOTICE, "VCC_CANCEL", mcode::VCC_CANCEL));
+            return exit_code_t::GENERAL_ERROR;
+        }
+    }
+
+    match usecase.delete(&name, matches.get_flag("backup")) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn default_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = if matches.get_flag("unset") { None } else { matches.get_one::<String>("name").map(String::as_str) };
+
+    match usecase.set_default(name) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn deactivate_environment(usecase: &dyn EnvironmentUsecase, _matches: &ArgMatches) -> exit_code_t {
+    match usecase.deactivate() {
+        Ok(response) => {
+            println!("{}", response.messages);
+            for path in &response.released {
+                println!("  released {}", path.display());
+            }
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn exec_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+    let command = strings(matches, "command");
+
+    match usecase.launch(Some(&name), true, true) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            run(&command[0], &command[1..], &response.variables)
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn edit_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("env").map(String::as_str);
+
+    match usecase.launch(name, true, true) {
+        Ok(response) => {
+            report_warnings(&response.warnings);
+            // The editor setting may carry its own arguments, e.g. "nvim -p"
+            let mut command: Vec<String> = response.editor.split_whitespace().map(str::to_string).collect();
+            if command.is_empty() {
+                return report_error(&vem_error_t::ConfigurationError(
+                    "the editor setting in config.toml has no command".to_string(),
+                ));
+            }
+            command.extend(strings(matches, "files"));
+            run(&command[0], &command[1..], &response.variables)
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn local_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").map(String::as_str);
+
+    if name.is_none() && !matches.get_flag("unset") {
+        let output = match output_format(matches) {
+            Ok(output) => output,
+            Err(err) => return report_error(&err),
+        };
+        return match usecase.get_local() {
+            Ok(response) => match &response.local {
+                Some(local) => {
+                    let items = [record(local, &[])];
+                    print_response(&output, &response, &items, || print_line!("{}", local.environment))
+                },
+                None => {
+                    eprintln!("{}", format_message(log_level_t::NOTICE, &response.mcode, &response.messages));
+                    exit_code_t::GENERAL_ERROR
+                },
+            },
+            Err(err) => report_error(&err),
+        };
+    }
+
+    match usecase.set_local(name) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn undelete_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+
+    match usecase.undelete(&name) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+// Run a program with extra environment variables; on Unix vem is replaced by it
+pub fn run(program: &str, args: &[String], variables: &[(String, String)]) -> exit_code_t {
+    let mut command = std::process::Command::new(program);
+    command.args(args);
+    command.envs(variables.iter().map(|(key, value)| (key, value)));
+
+    #[cfg(unix)]
+    let result = {
+        use std::os::unix::process::CommandExt;
+        Err::<std::process::ExitStatus, _>(command.exec())
+    };
+
+    #[cfg(not(unix))]
+    let result = command.status();
+
+    match result {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(err) => report_error(&vem_error_t::FileSystemError(std::io::Error::new(
+            err.kind(),
+            format!("failed to run '{}': {}", program, err),
+        ))),
+    }
+}
+
+// Ask a yes/no question on stderr; anything but y/yes (including EOF) is a no
+pub fn confirm(question: &str) -> bool {
+    eprint!("{}", format_message(log_level_t::NOTICE, "VCC_CONFIRM", question));
+    let _ = std::io::stderr().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+// Print an error to stderr and map it to the documented exit code
+pub fn report_error(err: &vem_error_t) -> exit_code_t {
+    eprintln!("{}", format_message(log_level_t::ERROR, "VCE2", &err.to_string()));
+    exit_code_t::from(err)
+}
+
+// Print the warnings from loading a vem.toml to stderr
+pub fn report_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("{}", format_message(log_level_t::WARN, "VMF1", warning));
+    }
+}
@@ -0,0 +1,175 @@
+use std::io::Write;
+
+use clap::ArgMatches;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::ctl::environment::report_error;
+use crate::util::error::{exit_code_t, vem_error_t};
+
+/// `println!` for table output; a reader that exits early (`| head`) closes the pipe, and that is not an error
+macro_rules! print_line {
+    ($($arg:tt)*) => {{
+        use std::io::Write as _;
+        let _ = writeln!(std::io::stdout().lock(), $($arg)*);
+    }};
+}
+pub(crate) use print_line;
+
+// Output mode selected by the global --output / --format options
+pub enum output_t {
+    TABLE,
+    JSON,
+    TOML,
+    TEMPLATE(String),
+}
+
+/// Resolve the output mode; --format implies template mode
+pub fn output_format(matches: &ArgMatches) -> Result<output_t, vem_error_t> {
+    let format = matches.get_one::<String>("format").cloned();
+    let output = matches.get_one::<String>("output").map(String::as_str);
+
+    match (output, format) {
+        (Some("json"), None) => Ok(output_t::JSON),
+        (Some("toml"), None) => Ok(output_t::TOML),
+        (Some("template") | Some("table") | None, Some(format)) => Ok(output_t::TEMPLATE(format)),
+        (Some("template"), None) => Err(vem_error_t::InvalidArgument("--output template requires --format".to_string())),
+        (Some(_), Some(_)) => Err(vem_error_t::InvalidArgument("--format only applies to --output template".to_string())),
+        _ => Ok(output_t::TABLE),
+    }
+}
+
+/// Print a read command's response in the selected mode.
+/// `items` are the per-line records used by template mode; `table` prints the human-readable form.
+pub fn print_response<T: Serialize>(
+    output: &output_t,
+    response: &T,
+    items: &[Value],
+    table: impl FnOnce(),
+) -> exit_code_t {
+    let rendered = match output {
+        output_t::TABLE => {
+            table();
+            return exit_code_t::SUCCESS;
+        },
+        output_t::JSON => serde_json::to_string_pretty(response)
+            .map_err(|e| vem_error_t::SerializationError(format!("Failed to serialize output: {}", e))),
+        output_t::TOML => toml::to_string_pretty(response)
+            .map(|s| s.trim_end().to_string())
+            .map_err(|e| vem_error_t::SerializationError(format!("Failed to serialize output: {}", e))),
+        output_t::TEMPLATE(format) => items
+            .iter()
+            .map(|item| render_template(format, item))
+            .collect::<Result<Vec<String>, vem_error_t>>()
+            .map(|lines| lines.join("\n")),
+    };
+
+    match rendered {
+        Ok(text) => {
+            // A reader that exits early (`| head`) closes the pipe; that is not an error for us
+            if !text.is_empty() {
+                let _ = writeln!(std::io::stdout().lock(), "{}", text);
+            }
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+/// Print rows (header first) in aligned columns; a non-empty marker is printed before each row
+pub fn print_table<R: AsRef<[String]>>(rows: &[R], marker: impl Fn(usize) -> &'static str) {
+    let mut widths = Vec::new();
+    for row in rows {
+        let row = row.as_ref();
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for (index, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .as_ref()
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        match marker(index) {
+            "" => print_line!("{}", cells.join("  ").trim_end()),
+            marker => print_line!("{} {}", marker, cells.join("  ").trim_end()),
+        }
+    }
+}
+
+/// Collect every value of a repeatable string argument
+pub fn strings(matches: &ArgMatches, id: &str) -> Vec<String> {
+    matches.get_many::<String>(id).map(|values| values.cloned().collect()).unwrap_or_default()
+}
+
+/// Serialize a model into a template record, optionally adding extra fields
+pub fn record<T: Serialize>(item: &T, extra: &[(&str, Value)]) -> Value {
+    let mut value = serde_json::to_value(item).unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut value {
+        for (key, extra_value) in extra {
+            map.insert(key.to_string(), extra_value.clone());
+        }
+    }
+    value
+}
+
+// Expand `{field}` placeholders and `\t`, `\n`, `\\` escapes; `{{` and `}}` are literal braces
+fn render_template(format: &str, item: &Value) -> Result<String, vem_error_t> {
+    let mut out = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('t') => out.push('\t'),
+                Some('n') => out.push('\n'),
+                Some('\\') => out.push('\\'),
+                Some(other) => {
+                    out.push('\\');
+                    out.push(other);
+                },
+                None => out.push('\\'),
+            },
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            },
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            },
+            '{' => {
+                let mut key = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(k) => key.push(k),
+                        None => {
+                            return Err(vem_error_t::InvalidArgument(format!("unterminated '{{{}' in --format", key)));
+                        },
+                    }
+                }
+                match item.get(&key) {
+                    Some(value) => out.push_str(&field_text(value)),
+                    None => return Err(vem_error_t::InvalidArgument(format!("unknown field '{}' in --format", key))),
+                }
+            },
+            _ => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+// Plain-text form of a field: null is empty, arrays are comma-joined
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(values) => values.iter().map(field_text).collect::<Vec<String>>().join(","),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,146 @@
+use clap::{
+    Arg,
+    ArgAction,
+    ArgGroup,
+    ArgMatches,
+    Command,
+};
+use clap_complete::engine::ArgValueCandidates;
+use crate::ctl::completion::environment_candidates;
+use crate::cnf::application::bin_root;
+use crate::ctl::environment::report_error;
+use crate::usc::environment::EnvironmentUsecase;
+use crate::util::error::{exit_code_t, vem_error_t};
+
+// Hook for bash and zsh: puts the shims on PATH and wraps `vem`, since `vem shell` prints code that must run in the calling shell
+const POSIX_HOOK: &str = r#"# vem shell integration ({shell})
+case ":$PATH:" in
+  *:{bin}:*) ;;
+  *) export PATH={bin}:"$PATH" ;;
+esac
+
+vem() {
+  if [ "$1" = "shell" ]; then
+    case " $* " in
+      *" -h "*|*" --help "*) command vem "$@"; return ;;
+    esac
+    shift
+    local script
+    script="$(VEM_SHELL={shell} command vem shell "$@")" || return
+    eval "$script"
+  else
+    command vem "$@"
+  fi
+}
+"#;
+
+// Same hook for fish
+const FISH_HOOK: &str = r#"# vem shell integration (fish)
+contains -- {bin} $PATH; or set -gx PATH {bin} $PATH
+
+function vem
+    if test (count $argv) -gt 0; and test "$argv[1]" = shell
+        if contains -- -h $argv; or contains -- --help $argv
+            command vem $argv
+            return
+        end
+        set -l script (env VEM_SHELL=fish vem $argv); or return
+        string join \n -- $script | source
+    else
+        command vem $argv
+    end
+end
+"#;
+
+pub fn init_shell_init_cmd() -> Command {
+    let mut shell_init = Command::new("shell-init");
+    shell_init = shell_init.about("Print the shell hook that enables `vem shell`");
+    shell_init = shell_init.arg_required_else_help(true);
+
+    let mut shell = Arg::new("shell");
+    shell = shell
+        .help("Shell to emit the hook for")
+        .required(true)
+        .value_name("SHELL")
+        .value_parser(["bash", "zsh", "fish"]);
+    shell_init = shell_init.arg(shell);
+
+    shell_init
+}
+
+pub fn init_shell_cmd() -> Command {
+    let mut shell = Command::new("shell");
+    shell = shell.about("Use an environment in this shell session only (needs `vem shell-init`)");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Environment to use in this shell")
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(environment_candidates));
+    shell = shell.arg(name);
+
+    let mut unset = Arg::new("unset");
+    unset = unset
+        .help("Stop using a per-shell environment")
+        .long("unset")
+        .action(ArgAction::SetTrue);
+    shell = shell.arg(unset);
+
+    let mut target = ArgGroup::new("target");
+    target = target
+        .args(["name", "unset"])
+        .required(true);
+    shell = shell.group(target);
+
+    shell
+}
+
+pub fn shell_init(matches: &ArgMatches) -> exit_code_t {
+    // Shims in VEM_HOME/bin must come before the real editors on PATH
+    let bin = bin_root().display().to_string();
+    match matches.get_one::<String>("shell").map(String::as_str) {
+        Some("fish") => print!("{}", FISH_HOOK.replace("{bin}", &quote(&bin, true))),
+        Some(shell) => print!("{}", POSIX_HOOK.replace("{shell}", shell).replace("{bin}", &quote(&bin, false))),
+        None => return exit_code_t::INVALID_ARGUMENTS,
+    }
+    exit_code_t::SUCCESS
+}
+
+pub fn shell_environment(usecase: &dyn EnvironmentUsecase, matches: &ArgMatches) -> exit_code_t {
+    // The hook says which shell it is; without it there is nobody to evaluate our output
+    let Some(shell) = std::env::var("VEM_SHELL").ok().filter(|shell| !shell.is_empty()) else {
+        return report_error(&vem_error_t::ConfigurationError(
+            "shell integration is not loaded; add `eval \"$(vem shell-init bash)\"` (or zsh/fish) to your shell startup file"
+                .to_string(),
+        ));
+    };
+    let fish = shell == "fish";
+
+    if matches.get_flag("unset") {
+        println!("{}", if fish { "set -e VEM_ENV" } else { "unset VEM_ENV" });
+        return exit_code_t::SUCCESS;
+    }
+
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+    match usecase.get(&name) {
+        Ok(response) => {
+            let value = quote(&response.environment.name, fish);
+            if fish {
+                println!("set -gx VEM_ENV {}", value);
+            } else {
+                println!("export VEM_ENV={}", value);
+            }
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+// Single-quote a value for the target shell
+fn quote(value: &str, fish: bool) -> String {
+    if fish {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
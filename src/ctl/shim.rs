@@ -0,0 +1,112 @@
+use clap::{
+    Arg,
+    ArgMatches,
+    Command,
+};
+use crate::ctl::environment::{report_error, run};
+use crate::ctl::output::{output_format, print_line, print_response, record};
+use crate::usc::shim::ShimUsecase;
+use crate::util::error::exit_code_t;
+use crate::util::mcode::{format_message, log_level_t};
+
+pub fn init_install_shim_cmd() -> Command {
+    let mut install = Command::new("install");
+    install = install.about("Create vim, nvim, gvim and view shims in VEM_HOME/bin");
+    install
+}
+
+pub fn init_which_shim_cmd() -> Command {
+    let mut which = Command::new("which");
+    which = which.about("Show the real editor and config a shim would use here");
+
+    let mut program = Arg::new("program");
+    program = program
+        .help("Shimmed program")
+        .required(true)
+        .value_name("PROGRAM")
+        .value_parser(["vim", "nvim", "gvim", "view"]);
+    which = which.arg(program);
+
+    which
+}
+
+pub fn init_exec_shim_cmd() -> Command {
+    let mut exec = Command::new("exec");
+    exec = exec.about("Run a shimmed program (used by the shims themselves)");
+    exec = exec.hide(true);
+
+    let mut program = Arg::new("program");
+    program = program
+        .help("Shimmed program")
+        .required(true)
+        .value_name("PROGRAM");
+    exec = exec.arg(program);
+
+    let mut args = Arg::new("args");
+    args = args
+        .help("Arguments passed to the program")
+        .value_name("ARGS")
+        .num_args(0..)
+        .trailing_var_arg(true)
+        .allow_hyphen_values(true);
+    exec = exec.arg(args);
+
+    exec
+}
+
+pub fn install_shim(usecase: &dyn ShimUsecase, _matches: &ArgMatches) -> exit_code_t {
+    match usecase.install() {
+        Ok(response) => {
+            for shim in &response.shims {
+                println!("{}", shim.display());
+            }
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn which_shim(usecase: &dyn ShimUsecase, matches: &ArgMatches) -> exit_code_t {
+    let output = match output_format(matches) {
+        Ok(output) => output,
+        Err(err) => return report_error(&err),
+    };
+    let program = matches.get_one::<String>("program").cloned().unwrap_or_default();
+
+    match usecase.resolve(&program, false) {
+        Ok(response) => {
+            report_shim_warnings(&response.warnings);
+            let items = [record(&response, &[])];
+            print_response(&output, &response, &items, || {
+                print_line!("{}", response.binary.display());
+                print_line!("  environment: {}", response.environment.as_deref().unwrap_or("(none)"));
+                match &response.config {
+                    Some(config) => print_line!("  config:      {}", config.display()),
+                    None => print_line!("  config:      (editor default)"),
+                }
+            })
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn exec_shim(usecase: &dyn ShimUsecase, matches: &ArgMatches) -> exit_code_t {
+    let program = matches.get_one::<String>("program").cloned().unwrap_or_default();
+    let args: Vec<String> = matches.get_many::<String>("args").map(|args| args.cloned().collect()).unwrap_or_default();
+
+    match usecase.resolve(&program, true) {
+        Ok(response) => {
+            report_shim_warnings(&response.warnings);
+            run(&response.binary.display().to_string(), &args, &response.variables)
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+// Print resolution warnings to stderr
+fn report_shim_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("{}", format_message(log_level_t::WARN, "VSW4", warning));
+    }
+}
@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use clap::{
+    Arg,
+    ArgAction,
+    ArgMatches,
+    Command,
+};
+use clap_complete::engine::ArgValueCandidates;
+use crate::ctl::completion::template_candidates;
+use crate::ctl::environment::{report_error, report_warnings};
+use crate::ctl::output::{output_format, print_line, print_response, record};
+use crate::ent::model::manifest::editor_type_t;
+use crate::ent::model::template::TEMPLATE;
+use crate::usc::template::TemplateUsecase;
+use crate::util::error::exit_code_t;
+
+pub fn init_list_template_cmd() -> Command {
+    let mut templates = Command::new("templates");
+    templates = templates.visible_alias("template");
+    templates = templates.about("List template resources");
+
+    let mut verbose = Arg::new("verbose");
+    verbose = verbose
+        .help("Show verbose output")
+        .short('v')
+        .long("verbose")
+        .action(ArgAction::SetTrue);
+    templates = templates.arg(verbose);
+
+    templates
+}
+
+pub fn init_show_template_cmd() -> Command {
+    let mut template = Command::new("template");
+    template = template.about("Show a template resource");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Template name")
+        .required(true)
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(template_candidates));
+    template = template.arg(name);
+
+    template
+}
+
+pub fn init_extract_template_cmd() -> Command {
+    let mut template = Command::new("template");
+    template = template.about("Extract a template resource into a directory");
+
+    let mut name = Arg::new("name");
+    name = name
+        .help("Template name")
+        .required(true)
+        .value_name("NAME")
+        .add(ArgValueCandidates::new(template_candidates));
+    template = template.arg(name);
+
+    let mut dir = Arg::new("dir");
+    dir = dir
+        .help("Destination directory")
+        .required(true)
+        .value_name("DIR");
+    template = template.arg(dir);
+
+    template
+}
+
+pub fn list_template(usecase: &dyn TemplateUsecase, matches: &ArgMatches) -> exit_code_t {
+    let verbose = matches.get_flag("verbose");
+    let output = match output_format(matches) {
+        Ok(output) => output,
+        Err(err) => return report_error(&err),
+    };
+
+    match usecase.list() {
+        Ok(response) => {
+            let items: Vec<_> = response.templates.iter().map(|template| record(template, &[])).collect();
+            print_response(&output, &response, &items, || {
+                if response.templates.is_empty() {
+                    print_line!("{}", response.messages);
+                }
+                for template in &response.templates {
+                    if verbose {
+                        let description = template.manifest.environment.description.as_deref().unwrap_or("");
+                        print_line!("{}\t{}\t{}", template.name, template.origin, description);
+                    } else {
+                        print_line!("{}", template.name);
+                    }
+                }
+            })
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn show_template(usecase: &dyn TemplateUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+    let output = match output_format(matches) {
+        Ok(output) => output,
+        Err(err) => return report_error(&err),
+    };
+
+    match usecase.get(&name) {
+        Ok(response) => {
+            let items = [record(&response.template, &[])];
+            print_response(&output, &response, &items, || print_template(&response.template))
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+pub fn extract_template(usecase: &dyn TemplateUsecase, matches: &ArgMatches) -> exit_code_t {
+    let name = matches.get_one::<String>("name").cloned().unwrap_or_default();
+    let dir = matches.get_one::<String>("dir").map(PathBuf::from).unwrap_or_default();
+
+    match usecase.extract(&name, &dir) {
+        Ok(response) => {
+            println!("{}", response.messages);
+            exit_code_t::SUCCESS
+        },
+        Err(err) => report_error(&err),
+    }
+}
+
+// Render the manifest summary shown by `vem show template`
+fn print_template(template: &TEMPLATE) {
+    let manifest = &template.manifest;
+    let editor = match manifest.editor.editor_type {
+        editor_type_t::VIM => "vim",
+        editor_type_t::NEOVIM => "neovim",
+    };
+
+    print_line!("Name:        {}", template.name);
+    print_line!("Source:      {}", template.origin);
+    if let Some(description) = &manifest.environment.description {
+        print_line!("Description: {}", description);
+    }
+    if let Some(version) = &manifest.environment.version {
+        print_line!("Version:     {}", version);
+    }
+    if let Some(author) = &manifest.environment.author {
+        print_line!("Author:      {}", author);
+    }
+    if !manifest.environment.tags.is_empty() {
+        print_line!("Tags:        {}", manifest.environment.tags.join(", "));
+    }
+    match &manifest.editor.config_file {
+        Some(config_file) => print_line!("Editor:      {} ({})", editor, config_file),
+        None => print_line!("Editor:      {}", editor),
+    }
+    if manifest.plugins.enabled
+        && let Some(plugin_manager) = &manifest.plugins.manager
+    {
+        print_line!("Plugins:     {}", plugin_manager);
+    }
+    if manifest.lsp.enabled {
+        let provider = manifest.lsp.provider.as_deref().unwrap_or("enabled");
+        print_line!("LSP:         {}", provider);
+    }
+    if let Some(theme) = &manifest.theme.name {
+        print_line!("Theme:       {}", theme);
+    }
+    if manifest.ctags.enabled {
+        let repositories: Vec<&str> = manifest.ctags.repositories.keys().map(String::as_str).collect();
+        let tag_sets: Vec<&str> = manifest.ctags.tags.keys().map(String::as_str).collect();
+        print_line!("Ctags:       repositories [{}], tag sets [{}]", repositories.join(", "), tag_sets.join(", "));
+    }
+    if !manifest.requirements.external_tools.is_empty() {
+        print_line!("Requires:    {}", manifest.requirements.external_tools.join(", "));
+    }
+
+    print_line!("Files:");
+    for file in &template.files {
+        print_line!("  {}", file.path.display());
+    }
+
+    report_warnings(&manifest.warnings);
+}
@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+/// Snapshot of an environment directory under VEM_HOME/backups/<environment>/<id>
+pub struct BACKUP {
+    pub environment: String,
+    pub id: String, // Directory name, a UTC timestamp such as 20260117T093000Z
+    pub created: chrono::DateTime<chrono::Utc>,
+    pub path: PathBuf,
+    pub size: u64,
+}
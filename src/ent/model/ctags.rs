@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+// How a tag file relates to the environment's vem.toml
+pub enum ctag_status_t {
+    ACTIVE,   // Repository is configured and enabled
+    DISABLED, // Repository is configured with enabled = false
+    ORPHANED, // Repository is no longer in vem.toml
+}
+
+/// Tag file generated for a [ctags.repositories.*] entry, kept under <environment>/ctags
+pub struct CTAG {
+    pub name: String, // Repository key in vem.toml
+    pub repository: String,
+    pub tag_file: PathBuf,
+    pub last_updated: chrono::DateTime<chrono::Utc>,
+    pub size: u64,
+    pub status: ctag_status_t,
+}
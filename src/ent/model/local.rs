@@ -0,0 +1,7 @@
+use std::path::PathBuf;
+
+/// A `.vem-env` file pinning an environment to a directory tree
+pub struct LOCAL {
+    pub environment: String,
+    pub path: PathBuf, // The .vem-env file itself
+}
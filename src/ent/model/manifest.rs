@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+
+// Editor flavour an environment is built for
+pub enum editor_type_t {
+    VIM,
+    NEOVIM,
+}
+
+/// Typed view of an environment's vem.toml
+pub struct MANIFEST {
+    pub environment: MANIFEST_ENVIRONMENT,
+    pub editor: EDITOR,
+    pub features: BTreeMap<String, bool>,
+    pub plugins: PLUGINS,
+    pub plugin_managers: PLUGIN_MANAGERS,
+    pub packages: PACKAGES,
+    pub ctags: CTAGS,
+    pub theme: THEME,
+    pub keymaps: KEYMAPS,
+    pub lsp: LSP,
+    pub ai_tools: AI_TOOLS,
+    pub treesitter: TREESITTER,
+    pub completion: COMPLETION,
+    pub git: GIT,
+    pub performance: PERFORMANCE,
+    pub compatibility: COMPATIBILITY,
+    pub requirements: REQUIREMENTS,
+    pub warnings: Vec<String>, // Diagnostics collected while loading (not part of the file)
+}
+
+// [environment]
+pub struct MANIFEST_ENVIRONMENT {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub env_type: Option<editor_type_t>,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub tags: Vec<String>,
+}
+
+// [editor]
+pub struct EDITOR {
+    pub editor_type: editor_type_t,
+    pub config_file: Option<String>,
+    pub global_configs: Vec<String>,
+}
+
+// [plugins]
+pub struct PLUGINS {
+    pub enabled: bool,
+    pub manager: Option<String>,
+    pub auto_install: bool,
+}
+
+// [plugin_managers] and [plugin_managers.configs.*]
+pub struct PLUGIN_MANAGERS {
+    pub selected: BTreeMap<String, bool>,
+    pub configs: BTreeMap<String, PLUGIN_MANAGER_CONFIG>,
+}
+
+pub struct PLUGIN_MANAGER_CONFIG {
+    pub url: Option<String>,
+    pub install_path: Option<String>,
+    pub config_setup: Option<String>,
+    pub config_line: Option<String>,
+    pub config_block_start: Option<String>,
+    pub config_block_end: Option<String>,
+    pub install_command: Option<String>,
+    pub update_command: Option<String>,
+    pub clean_command: Option<String>,
+    pub packages: Vec<PLUGIN_PACKAGE>,
+}
+
+pub struct PLUGIN_PACKAGE {
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub dependencies: Vec<String>,
+    pub build: Option<String>,
+    pub config: Option<String>,
+    pub priority: Option<u32>,
+}
+
+// [packages]
+pub struct PACKAGES {
+    pub system: Vec<SYSTEM_PACKAGE>,
+}
+
+pub struct SYSTEM_PACKAGE {
+    pub name: String,
+    pub package_managers: BTreeMap<String, String>,
+}
+
+// [ctags]
+pub struct CTAGS {
+    pub enabled: bool,
+    pub executable: String,
+    pub global_config_file: Option<String>,
+    pub auto_generate: bool,
+    pub update_on_save: bool,
+    pub ai_enhanced: bool,
+    pub global: CTAGS_GLOBAL,
+    pub repositories: BTreeMap<String, CTAGS_REPOSITORY>,
+    pub tags: BTreeMap<String, CTAGS_TAG_SET>,
+    pub commands: CTAGS_COMMANDS,
+    pub gutentags: CTAGS_GUTENTAGS,
+    pub ai_integration: BTreeMap<String, String>,
+    pub lsp_integration: BTreeMap<String, String>,
+}
+
+// [ctags.global]
+pub struct CTAGS_GLOBAL {
+    pub languages: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+    pub custom_options: Vec<String>,
+}
+
+// [ctags.repositories.*]
+pub struct CTAGS_REPOSITORY {
+    pub name: String,
+    pub description: Option<String>,
+    pub path: String,
+    pub remote_url: Option<String>,
+    pub branch: Option<String>,
+    pub enabled: bool,
+    pub auto_sync: bool,
+    pub priority: u32,
+}
+
+// [ctags.tags.*]
+pub struct CTAGS_TAG_SET {
+    pub name: String,
+    pub description: Option<String>,
+    pub tag_file: String,
+    pub repositories: Vec<String>,
+    pub source_dirs: Vec<String>,
+    pub languages: Vec<String>,
+    pub include_patterns: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+    pub custom_options: Vec<String>,
+    pub auto_generate: bool,
+    pub project_root_markers: Vec<String>,
+    pub ai_context: bool,
+    pub ai_priority: Option<String>,
+}
+
+// [ctags.commands]
+pub struct CTAGS_COMMANDS {
+    pub generate_command: Option<String>,
+    pub generate_options: Vec<String>,
+    pub update_command: Option<String>,
+    pub update_options: Vec<String>,
+    pub delete_command: Option<String>,
+    pub delete_options: Vec<String>,
+    pub list_format: Option<String>,
+    pub list_fields: Vec<String>,
+    pub clean_backup: bool,
+    pub clean_confirm: bool,
+    pub ai_enhance: bool,
+    pub ai_context_update: bool,
+    pub ai_context_cleanup: bool,
+    pub ai_context_reset: bool,
+}
+
+// [ctags.gutentags]
+pub struct CTAGS_GUTENTAGS {
+    pub enabled: bool,
+    pub project_root_markers: Vec<String>,
+    pub cache_dir: Option<String>,
+    pub exclude_dirs: Vec<String>,
+}
+
+// [theme]
+pub struct THEME {
+    pub name: Option<String>,
+    pub variant: Option<String>,
+    pub background: Option<String>,
+    pub airline_theme: Option<String>,
+}
+
+// [keymaps]
+pub struct KEYMAPS {
+    pub leader: Option<String>,
+    pub custom_maps: Vec<KEYMAP>,
+}
+
+pub struct KEYMAP {
+    pub key: String,
+    pub action: String,
+    pub mode: Option<String>,
+    pub map_type: Option<String>,
+}
+
+// [lsp]
+pub struct LSP {
+    pub enabled: bool,
+    pub provider: Option<String>,
+    pub auto_install: bool,
+    pub languages: Vec<String>,
+}
+
+// [ai_tools] and [ai_tools.*]
+pub struct AI_TOOLS {
+    pub enabled: bool,
+    pub tools: BTreeMap<String, AI_TOOL>,
+}
+
+pub struct AI_TOOL {
+    pub enabled: bool,
+    pub options: BTreeMap<String, String>,
+}
+
+// [treesitter]
+pub struct TREESITTER {
+    pub enabled: bool,
+    pub auto_install: bool,
+    pub languages: Vec<String>,
+}
+
+// [completion]
+pub struct COMPLETION {
+    pub enabled: bool,
+    pub sources: Vec<String>,
+    pub snippet_engine: Option<String>,
+}
+
+// [git]
+pub struct GIT {
+    pub enabled: bool,
+    pub show_changes: bool,
+    pub signs: bool,
+    pub fugitive_maps: bool,
+}
+
+// [performance]
+pub struct PERFORMANCE {
+    pub swap_files: bool,
+    pub backup_files: bool,
+    pub backup_dir: Option<String>,
+    pub undo_levels: Option<u32>,
+    pub update_time: Option<u32>,
+}
+
+// [compatibility]
+pub struct COMPATIBILITY {
+    pub vim_version: Option<String>,
+    pub neovim_version: Option<String>,
+    pub neovim_compatible: bool,
+}
+
+// [requirements]
+pub struct REQUIREMENTS {
+    pub environment_vars: Vec<String>,
+    pub external_tools: Vec<String>,
+}
@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+use crate::ent::model::manifest::MANIFEST;
+
+/// Environment template: its parsed vem.toml plus every file to install
+pub struct TEMPLATE {
+    pub name: String,
+    pub origin: String, // "bundled" or the directory it was loaded from
+    pub manifest: MANIFEST,
+    pub files: Vec<TEMPLATE_FILE>,
+}
+
+// A file inside a template, relative to the template root
+pub struct TEMPLATE_FILE {
+    pub path: PathBuf,
+    pub contents: Vec<u8>,
+}
@@ -0,0 +1,9 @@
+/// Request for `generate ctags` and `update ctags`
+pub struct CTAGS_GENERATE {
+    pub environment: Option<String>, // Current environment when unset
+    pub repository: Option<String>,  // Every enabled repository when unset
+    pub tag_set: Option<String>,     // [ctags.tags.*] set to generate instead of a repository (generate only)
+    pub languages: Vec<String>,
+    pub exclude: Vec<String>,
+    pub force: bool, // Full rebuild instead of re-tagging changed files (update only)
+}
@@ -1,5 +1,33 @@
+use crate::ent::model::manifest::editor_type_t;
+
 pub struct ENVIRONMENT {
     pub name: String,
     pub description: Option<String>,
     pub tags: Vec<String>,
-}
\ No newline at end of file
+    pub template: Option<String>,
+    pub editor: Option<editor_type_t>, // Layout to scaffold when no template is given
+}
+
+pub struct ENVIRONMENT_UPDATE {
+    pub name: String,
+    pub description: Option<String>,
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+    pub clear_tags: bool,
+}
+
+// Ordering applied by `list envs --sort`
+pub enum environment_sort_t {
+    NAME,
+    CREATED,
+    UPDATED,
+    LAST_USED,
+}
+
+pub struct ENVIRONMENT_LIST {
+    pub tags: Vec<String>,
+    pub sort: environment_sort_t,
+    pub reverse: bool,
+    pub current_first: bool,
+    pub with_size: bool,
+}
@@ -0,0 +1,13 @@
+use crate::ent::model::backup::BACKUP as ModelBackup;
+
+pub struct BACKUP {
+    pub mcode: String,
+    pub messages: String,
+    pub backup: ModelBackup
+}
+
+pub struct BACKUPS {
+    pub mcode: String,
+    pub messages: String,
+    pub backups: Vec<ModelBackup>
+}
@@ -0,0 +1,10 @@
+use crate::ent::model::ctags::CTAG;
+
+pub struct CTAGS {
+    pub mcode: String,
+    pub messages: String,
+    pub environment: String,
+    pub tags: Vec<CTAG>,
+    pub fields: Vec<String>, // Columns for `list ctags`, from [ctags.commands] list_fields
+    pub warnings: Vec<String>, // From loading vem.toml; printed on stderr, not part of the output
+}
@@ -1,8 +1,64 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 use crate::ent::model::environment::ENVIRONMENT as ModelEnvironment;
+use crate::ent::model::local::LOCAL;
 
 pub struct ENVIRONMENT {
     pub mcode: String,
     pub messages: String,
-    pub environment: ModelEnvironment
-}
\ No newline at end of file
+    pub environment: ModelEnvironment,
+    pub warnings: Vec<String>, // From loading vem.toml; printed on stderr, not part of the output
+}
+
+pub struct ENVIRONMENTS {
+    pub mcode: String,
+    pub messages: String,
+    pub environments: Vec<ModelEnvironment>,
+    pub current: Option<String>,
+    pub sizes: BTreeMap<String, u64>, // On-disk bytes per environment, filled only when requested
+    pub warnings: Vec<String>,        // Environments skipped because they cannot be read
+}
+
+pub struct ENVIRONMENT_DEFAULT {
+    pub mcode: String,
+    pub messages: String,
+    pub environment: Option<ModelEnvironment>
+}
+
+pub struct ENVIRONMENT_DEACTIVATE {
+    pub mcode: String,
+    pub messages: String,
+    pub environment: Option<ModelEnvironment>,
+    pub released: Vec<PathBuf>, // Dotfile links removed (originals restored where saved)
+}
+
+pub struct ENVIRONMENT_LAUNCH {
+    pub mcode: String,
+    pub messages: String,
+    pub environment: ModelEnvironment,
+    pub variables: Vec<(String, String)>, // Exported to the launched process
+    pub editor: String,                   // app_config.editor, used by `vem edit`
+    pub warnings: Vec<String>, // From loading vem.toml; printed on stderr, not part of the output
+}
+
+pub struct ENVIRONMENT_LOCAL {
+    pub mcode: String,
+    pub messages: String,
+    pub local: Option<LOCAL>,
+}
+
+// One step of current-environment resolution, as reported by `current env --explain`
+pub struct CURRENT_SOURCE {
+    pub source: String,              // VEM_ENV, .vem-env, current or default_environment
+    pub environment: Option<String>, // Name this source gives, if any
+    pub detail: Option<String>,      // e.g. the .vem-env path
+    pub selected: bool,
+}
+
+pub struct ENVIRONMENT_EXPLAIN {
+    pub mcode: String,
+    pub messages: String,
+    pub environment: Option<ModelEnvironment>,
+    pub sources: Vec<CURRENT_SOURCE>, // In resolution order
+}
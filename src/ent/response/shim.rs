@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+pub struct SHIMS {
+    pub mcode: String,
+    pub messages: String,
+    pub bin: PathBuf, // Directory to put first on PATH
+    pub shims: Vec<PathBuf>,
+}
+
+pub struct SHIM {
+    pub mcode: String,
+    pub messages: String,
+    pub program: String,
+    pub binary: PathBuf,                  // Real editor the shim runs
+    pub environment: Option<String>,      // None when no environment is active
+    pub config: Option<PathBuf>,          // vimrc / init.lua the editor will load; None means its own default
+    pub variables: Vec<(String, String)>, // Exported to the editor
+    pub warnings: Vec<String>,            // Printed on stderr before the editor starts
+}
@@ -0,0 +1,13 @@
+use crate::ent::model::template::TEMPLATE as ModelTemplate;
+
+pub struct TEMPLATE {
+    pub mcode: String,
+    pub messages: String,
+    pub template: ModelTemplate
+}
+
+pub struct TEMPLATES {
+    pub mcode: String,
+    pub messages: String,
+    pub templates: Vec<ModelTemplate>
+}
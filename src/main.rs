@@ -1,9 +1,16 @@
 include!("mod.rs");
 mod base;
 use crate::base::BaseCmd;
+use crate::ctl::completion::COMPLETE_VAR;
+use crate::ctl::dispatch::dispatch;
+use clap_complete::CompleteEnv;
 
 fn main() {
+    // Answers the shell's completion requests and exits; a normal run falls through
+    CompleteEnv::with_factory(|| BaseCmd.execute()).var(COMPLETE_VAR).complete();
+
     let base_cmd = BaseCmd;
-    // Build CLI and parse (discard result for now to avoid unused warnings)
-    let _matches = base_cmd.execute().get_matches();
+    let matches = base_cmd.execute().get_matches();
+    let code = dispatch(&matches);
+    std::process::exit(code as i32);
 }
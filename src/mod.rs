@@ -11,31 +11,98 @@ pub mod cnf {
 
 pub mod ent {
 	pub mod model {
+		pub mod backup;
+		pub mod ctags;
 		pub mod environment;
+		pub mod local;
+		pub mod manifest;
+		pub mod template;
 	}
 	pub mod request {
+		pub mod ctags;
 		pub mod environment;
 	}
 	pub mod response {
+		pub mod backup;
+		pub mod ctags;
 		pub mod environment;
+		pub mod shim;
+		pub mod template;
 	}
 	pub use crate::util::error::vem_error_t;
+	pub use model::backup::BACKUP;
+	pub use model::ctags::CTAG;
 	pub use model::environment::ENVIRONMENT;
+	pub use model::local::LOCAL;
+	pub use model::manifest::MANIFEST;
+	pub use model::template::TEMPLATE;
 }
 
 pub mod rep {
+	pub mod activation;
+	pub mod backup;
+	pub mod ctags;
 	pub mod environment;
+	pub mod local;
+	pub mod manifest;
+	pub mod shim;
+	pub mod template;
+	pub use activation::activation_repository;
+	pub use backup::backup_repository;
+	pub use ctags::ctags_repository;
 	pub use environment::environment_repository;
+	pub use local::local_repository;
+	pub use manifest::manifest_repository;
+	pub use shim::shim_repository;
+	pub use template::template_repository;
+	pub type ActivationRepository = dyn activation::ActivationRepository;
+	pub type BackupRepository = dyn backup::BackupRepository;
+	pub type CtagsRepository = dyn ctags::CtagsRepository;
 	pub type EnvironmentRepository = dyn environment::EnvironmentRepository;
+	pub type LocalRepository = dyn local::LocalRepository;
+	pub type ManifestRepository = dyn manifest::ManifestRepository;
+	pub type ShimRepository = dyn shim::ShimRepository;
+	pub type TemplateRepository = dyn template::TemplateRepository;
 }
 
 pub mod usc {
+	pub mod backup;
+	pub mod ctags;
 	pub mod environment;
-	pub use environment::EnvironmentUsecase;
+	pub mod shim;
+	pub mod template;
+	pub use backup::{
+		BackupUsecase,
+		backup_usecase
+	};
+	pub use ctags::{
+		CtagsUsecase,
+		ctags_usecase
+	};
+	pub use environment::{
+		EnvironmentUsecase,
+		environment_usecase
+	};
+	pub use shim::{
+		ShimUsecase,
+		shim_usecase
+	};
+	pub use template::{
+		TemplateUsecase,
+		template_usecase
+	};
 }
 
 pub mod ctl {
+	pub mod backup;
+	pub mod completion;
+	pub mod ctags;
+	pub mod dispatch;
 	pub mod environment;
+	pub mod output;
+	pub mod shell;
+	pub mod shim;
+	pub mod template;
 }
 
 pub mod util {
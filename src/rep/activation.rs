@@ -0,0 +1,291 @@
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use crate::cnf::application::{
+    app_config,
+    current_link_path,
+    dotfiles_backup_root,
+    home_path,
+    xdg_config_home,
+};
+use crate::ent::model::manifest::{MANIFEST, editor_type_t};
+use crate::rep::environment::{RepositoryConfig, validate_name};
+use crate::rep::manifest::{self as manifest_rep, ManifestRepository};
+use crate::util::error::vem_error_t;
+
+/// Activation repository trait: the editor-facing links into VEM_HOME/current
+pub trait ActivationRepository {
+    fn activate(&self, name: &str) -> Result<Vec<PathBuf>, vem_error_t>;
+    fn deactivate(&self, name: Option<&str>) -> Result<Vec<PathBuf>, vem_error_t>;
+    fn forget(&self, name: &str) -> Result<(), vem_error_t>;
+    fn launch_variables(&self, name: &str) -> Result<Vec<(String, String)>, vem_error_t>;
+    fn warnings(&self, name: &str) -> Result<Vec<String>, vem_error_t>;
+}
+
+/// Activation repository implementation with embedded config
+pub struct activation_repository {
+    base: RepositoryConfig,
+}
+
+impl activation_repository {
+    pub fn new(config: app_config) -> Self {
+        Self {
+            base: RepositoryConfig::new(config),
+        }
+    }
+}
+
+impl Deref for activation_repository {
+    type Target = RepositoryConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+// A dotfile vem may replace with a link into `current`
+struct link_target_t {
+    path: PathBuf, // e.g. ~/.vimrc
+    dest: PathBuf, // e.g. VEM_HOME/current/.vimrc
+    backup_name: &'static str, // Name of the saved original under VEM_HOME/dotfiles
+    editor_type: editor_type_t,
+}
+
+impl ActivationRepository for activation_repository {
+    /// Link the dotfiles the environment's editor reads, releasing those of the other editor
+    fn activate(&self, name: &str) -> Result<Vec<PathBuf>, vem_error_t> {
+        let editor_type = self.editor_type(name)?;
+
+        let mut linked = Vec::new();
+        for target in link_targets() {
+            let wanted = matches!(
+                (&target.editor_type, &editor_type),
+                (editor_type_t::VIM, editor_type_t::VIM) | (editor_type_t::NEOVIM, editor_type_t::NEOVIM)
+            );
+            if wanted {
+                link(&target)?;
+                linked.push(target.path);
+            } else {
+                release(&target)?;
+            }
+        }
+
+        if let editor_type_t::NEOVIM = editor_type {
+            linked.push(self.link_appname(name)?);
+        }
+        Ok(linked)
+    }
+
+    /// Remove vem's links, including the NVIM_APPNAME link of the environment `name` that was active,
+    /// and put the saved originals back
+    fn deactivate(&self, name: Option<&str>) -> Result<Vec<PathBuf>, vem_error_t> {
+        let mut released = Vec::new();
+        for target in link_targets() {
+            if release(&target)? {
+                released.push(target.path);
+            }
+        }
+
+        if let Some(name) = name {
+            validate_name(name)?;
+            let path = xdg_config_home().join(nvim_appname(name));
+            if is_ours(&path, &self.config().environment_root().join(name)) {
+                fs::remove_file(&path)?;
+                released.push(path);
+            }
+        }
+        Ok(released)
+    }
+
+    /// Remove the environment's NVIM_APPNAME config link, if vem created one
+    fn forget(&self, name: &str) -> Result<(), vem_error_t> {
+        validate_name(name)?;
+        let path = xdg_config_home().join(nvim_appname(name));
+        if is_ours(&path, &self.config().environment_root().join(name)) {
+            fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Variables that make an editor started outside `current` load this environment
+    fn launch_variables(&self, name: &str) -> Result<Vec<(String, String)>, vem_error_t> {
+        let mut variables = vec![("VEM_ENV".to_string(), name.to_string())];
+
+        match self.editor_type(name)? {
+            editor_type_t::VIM => {
+                // VIMINIT replaces ~/.vimrc; the environment's .vim stands in for ~/.vim (which may belong to
+                // the globally active environment) on the runtime and package paths
+                let env_path = self.config().environment_root().join(name);
+                let vim_dir = option_value(&env_path.join(".vim"));
+                variables.push(("MYVIMRC".to_string(), env_path.join(".vimrc").display().to_string()));
+                variables.push((
+                    "VIMINIT".to_string(),
+                    format!(
+                        concat!(
+                            "set runtimepath-=~/.vim runtimepath-=~/.vim/after packpath-=~/.vim packpath-=~/.vim/after",
+                            " | set runtimepath^={0} runtimepath+={0}/after packpath^={0} packpath+={0}/after",
+                            " | source $MYVIMRC",
+                        ),
+                        vim_dir
+                    ),
+                ));
+            },
+            editor_type_t::NEOVIM => {
+                self.link_appname(name)?;
+                variables.push(("NVIM_APPNAME".to_string(), nvim_appname(name)));
+            },
+        }
+        Ok(variables)
+    }
+
+    /// Unknown-key warnings from the environment's vem.toml
+    fn warnings(&self, name: &str) -> Result<Vec<String>, vem_error_t> {
+        Ok(self.manifest(name)?.map(|manifest| manifest.warnings).unwrap_or_default())
+    }
+}
+
+// Private helper methods
+impl activation_repository {
+    /// The environment's vem.toml, if it has one
+    fn manifest(&self, name: &str) -> Result<Option<MANIFEST>, vem_error_t> {
+        validate_name(name)?;
+        let manifest_path = self.config().environment_root().join(name).join("vem.toml");
+        if !manifest_path.is_file() {
+            return Ok(None);
+        }
+        Ok(Some(manifest_rep::new().load(&manifest_path)?))
+    }
+
+    /// Editor type from the environment's vem.toml; environments without one are Vim
+    fn editor_type(&self, name: &str) -> Result<editor_type_t, vem_error_t> {
+        Ok(self.manifest(name)?.map(|manifest| manifest.editor.editor_type).unwrap_or(editor_type_t::VIM))
+    }
+
+    /// Link XDG_CONFIG_HOME/vem-<name> to the environment so `NVIM_APPNAME=vem-<name> nvim`
+    /// gets its own config, data, state and cache directories
+    fn link_appname(&self, name: &str) -> Result<PathBuf, vem_error_t> {
+        let path = xdg_config_home().join(nvim_appname(name));
+        let dest = self.config().environment_root().join(name);
+        if is_ours(&path, &dest) {
+            return Ok(path);
+        }
+        if fs::symlink_metadata(&path).is_ok() {
+            return Err(vem_error_t::ConfigurationError(format!(
+                "{} exists and is not managed by vem; move it away to use NVIM_APPNAME isolation",
+                path.display()
+            )));
+        }
+
+        fs::create_dir_all(xdg_config_home())?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&dest, &path)?;
+
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(&dest, &path)?;
+
+        Ok(path)
+    }
+}
+
+fn link_targets() -> Vec<link_target_t> {
+    let current = current_link_path();
+    vec![
+        link_target_t {
+            path: home_path().join(".vimrc"),
+            dest: current.join(".vimrc"),
+            backup_name: ".vimrc",
+            editor_type: editor_type_t::VIM,
+        },
+        link_target_t {
+            path: home_path().join(".vim"),
+            dest: current.join(".vim"),
+            backup_name: ".vim",
+            editor_type: editor_type_t::VIM,
+        },
+        link_target_t {
+            path: xdg_config_home().join("nvim"),
+            dest: current,
+            backup_name: "nvim",
+            editor_type: editor_type_t::NEOVIM,
+        },
+    ]
+}
+
+/// NVIM_APPNAME that gives a Neovim environment its own stdpath() directories
+pub fn nvim_appname(name: &str) -> String {
+    format!("vem-{}", name)
+}
+
+// Escape a path for use as a Vim `:set` option value
+fn option_value(path: &Path) -> String {
+    let mut value = String::new();
+    for c in path.display().to_string().chars() {
+        if matches!(c, ' ' | ',' | '\\' | '|' | '"') {
+            value.push('\\');
+        }
+        value.push(c);
+    }
+    value
+}
+
+// Whether `path` is a symlink vem created for `dest`
+fn is_ours(path: &Path, dest: &Path) -> bool {
+    fs::read_link(path).is_ok_and(|target| target == dest)
+}
+
+// Point target.path at target.dest, saving whatever was there first
+fn link(target: &link_target_t) -> Result<(), vem_error_t> {
+    if is_ours(&target.path, &target.dest) {
+        return Ok(());
+    }
+
+    if fs::symlink_metadata(&target.path).is_ok() {
+        let backup = dotfiles_backup_root().join(target.backup_name);
+        if fs::symlink_metadata(&backup).is_ok() {
+            return Err(vem_error_t::ConfigurationError(format!(
+                "refusing to replace {}: a saved original already exists at {}",
+                target.path.display(),
+                backup.display()
+            )));
+        }
+        fs::create_dir_all(dotfiles_backup_root())?;
+        fs::rename(&target.path, &backup)?;
+    }
+
+    if let Some(parent) = target.path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&target.dest, &target.path)?;
+
+    #[cfg(windows)]
+    {
+        if target.backup_name == ".vimrc" {
+            std::os::windows::fs::symlink_file(&target.dest, &target.path)?;
+        } else {
+            std::os::windows::fs::symlink_dir(&target.dest, &target.path)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Remove vem's link at target.path and restore the saved original; false when there was no link
+fn release(target: &link_target_t) -> Result<bool, vem_error_t> {
+    if !is_ours(&target.path, &target.dest) {
+        return Ok(false);
+    }
+
+    fs::remove_file(&target.path)?;
+    let backup = dotfiles_backup_root().join(target.backup_name);
+    if fs::symlink_metadata(&backup).is_ok() {
+        fs::rename(&backup, &target.path)?;
+    }
+    Ok(true)
+}
+
+/// Factory function to create activation repository
+pub fn new(config: app_config) -> impl ActivationRepository {
+    activation_repository::new(config)
+}
@@ -0,0 +1,220 @@
+use std::fs;
+use std::ops::Deref;
+
+use crate::cnf::application::{app_config, backup_root};
+use crate::ent::model::backup::BACKUP;
+use crate::rep::environment::{RepositoryConfig, copy_tree, directory_size, validate_name};
+use crate::util::error::vem_error_t;
+
+// Backup directory names: sortable UTC timestamps, with a ".N" suffix on collisions
+const BACKUP_ID_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Backup repository trait
+pub trait BackupRepository {
+    fn create(&self, name: &str) -> Result<BACKUP, vem_error_t>;
+    fn snapshot(&self, name: &str) -> Result<Option<BACKUP>, vem_error_t>;
+    fn list(&self, name: Option<&str>) -> Result<Vec<BACKUP>, vem_error_t>;
+    fn get(&self, name: &str, id: Option<&str>) -> Result<BACKUP, vem_error_t>;
+    fn restore(&self, backup: &BACKUP) -> Result<(), vem_error_t>;
+    fn prune(&self, name: Option<&str>, retention_days: u32) -> Result<Vec<BACKUP>, vem_error_t>;
+}
+
+/// Backup repository implementation with embedded config
+pub struct backup_repository {
+    base: RepositoryConfig,
+}
+
+impl backup_repository {
+    pub fn new(config: app_config) -> Self {
+        Self {
+            base: RepositoryConfig::new(config),
+        }
+    }
+}
+
+impl Deref for backup_repository {
+    type Target = RepositoryConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl BackupRepository for backup_repository {
+    /// Copy an environment directory into a new timestamped backup
+    fn create(&self, name: &str) -> Result<BACKUP, vem_error_t> {
+        validate_name(name)?;
+        let env_path = self.config().environment_root().join(name);
+        if !env_path.is_dir() {
+            return Err(vem_error_t::EnvironmentNotFound(name.to_string()));
+        }
+
+        let created = chrono::Utc::now();
+        let env_backups = backup_root().join(name);
+        fs::create_dir_all(&env_backups)?;
+
+        let stamp = created.format(BACKUP_ID_FORMAT).to_string();
+        let mut id = stamp.clone();
+        let mut suffix = 1;
+        while env_backups.join(&id).exists() {
+            suffix += 1;
+            id = format!("{}.{}", stamp, suffix);
+        }
+
+        let path = env_backups.join(&id);
+        if let Err(err) = copy_tree(&env_path, &path) {
+            let _ = fs::remove_dir_all(&path);
+            return Err(err);
+        }
+
+        Ok(BACKUP {
+            environment: name.to_string(),
+            id,
+            created,
+            size: directory_size(&path)?,
+            path,
+        })
+    }
+
+    /// Automatic backup before a destructive operation, skipped when backup_enabled is off
+    fn snapshot(&self, name: &str) -> Result<Option<BACKUP>, vem_error_t> {
+        if !self.config().backup_enabled {
+            return Ok(None);
+        }
+        self.create(name).map(Some)
+    }
+
+    /// List backups, oldest first, for one environment or all of them
+    fn list(&self, name: Option<&str>) -> Result<Vec<BACKUP>, vem_error_t> {
+        let root = backup_root();
+        let mut backups = Vec::new();
+        if !root.is_dir() {
+            return Ok(backups);
+        }
+
+        let environments = match name {
+            Some(name) => {
+                validate_name(name)?;
+                vec![name.to_string()]
+            },
+            None => {
+                let mut names = Vec::new();
+                for entry in fs::read_dir(&root)? {
+                    let entry = entry?;
+                    if entry.file_type()?.is_dir()
+                        && let Some(name) = entry.file_name().to_str()
+                    {
+                        names.push(name.to_string());
+                    }
+                }
+                names.sort();
+                names
+            },
+        };
+
+        for environment in environments {
+            let env_backups = root.join(&environment);
+            if !env_backups.is_dir() {
+                continue;
+            }
+            let mut entries = Vec::new();
+            for entry in fs::read_dir(&env_backups)? {
+                let entry = entry?;
+                if entry.file_type()?.is_dir()
+                    && let Some(id) = entry.file_name().to_str()
+                    && let Some(created) = parse_id(id)
+                {
+                    entries.push(BACKUP {
+                        environment: environment.clone(),
+                        id: id.to_string(),
+                        created,
+                        size: directory_size(&entry.path())?,
+                        path: entry.path(),
+                    });
+                }
+            }
+            entries.sort_by(|a, b| a.created.cmp(&b.created).then_with(|| a.id.cmp(&b.id)));
+            backups.extend(entries);
+        }
+
+        Ok(backups)
+    }
+
+    /// Get a backup by id, or the most recent one for the environment
+    fn get(&self, name: &str, id: Option<&str>) -> Result<BACKUP, vem_error_t> {
+        let backups = self.list(Some(name))?;
+        let backup = match id {
+            Some(id) => backups.into_iter().find(|backup| backup.id == id),
+            None => backups.into_iter().next_back(),
+        };
+        backup.ok_or_else(|| vem_error_t::BackupNotFound(format!("{}/{}", name, id.unwrap_or("latest"))))
+    }
+
+    /// Replace the environment directory with the backup's contents
+    fn restore(&self, backup: &BACKUP) -> Result<(), vem_error_t> {
+        let env_root = self.config().environment_root();
+        let env_path = env_root.join(&backup.environment);
+        let staging = env_root.join(format!(".{}.restoring", backup.environment));
+        let previous = env_root.join(format!(".{}.previous", backup.environment));
+
+        // Copy first so a failed copy leaves the environment untouched
+        for leftover in [&staging, &previous] {
+            if leftover.exists() {
+                fs::remove_dir_all(leftover)?;
+            }
+        }
+        if let Err(err) = copy_tree(&backup.path, &staging) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(err);
+        }
+
+        if env_path.exists() {
+            fs::rename(&env_path, &previous)?;
+        }
+        fs::rename(&staging, &env_path)?;
+        if previous.exists() {
+            fs::remove_dir_all(&previous)?;
+        }
+        Ok(())
+    }
+
+    /// Delete backups older than the retention window; 0 days keeps everything
+    fn prune(&self, name: Option<&str>, retention_days: u32) -> Result<Vec<BACKUP>, vem_error_t> {
+        if retention_days == 0 {
+            return Ok(Vec::new());
+        }
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days));
+        let mut pruned = Vec::new();
+        for backup in self.list(name)? {
+            if backup.created < cutoff {
+                fs::remove_dir_all(&backup.path)?;
+                pruned.push(backup);
+            }
+        }
+
+        // Drop per-environment directories left empty
+        for backup in &pruned {
+            if let Some(parent) = backup.path.parent()
+                && parent.is_dir()
+                && fs::read_dir(parent)?.next().is_none()
+            {
+                fs::remove_dir(parent)?;
+            }
+        }
+        Ok(pruned)
+    }
+}
+
+// Parse the timestamp part of a backup id
+fn parse_id(id: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let stamp = id.split('.').next()?;
+    chrono::NaiveDateTime::parse_from_str(stamp, BACKUP_ID_FORMAT)
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// Factory function to create backup repository
+pub fn new(config: app_config) -> impl BackupRepository {
+    backup_repository::new(config)
+}
@@ -0,0 +1,786 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::{Duration, UNIX_EPOCH};
+
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher};
+use sha2::{Digest, Sha256};
+
+use crate::cnf::application::{app_config, current_link_path, current_state_path, expand_home};
+use crate::ent::model::ctags::{CTAG, ctag_status_t};
+use crate::ent::model::manifest::{MANIFEST, editor_type_t};
+use crate::rep::environment::{RepositoryConfig, validate_name};
+use crate::rep::manifest::{self as manifest_rep, ManifestRepository};
+use crate::util::error::vem_error_t;
+
+// Extension of per-repository tag files under <environment>/ctags
+const TAG_EXTENSION: &str = "tags";
+// Extension of the file manifest kept next to each tag file for incremental updates
+const FILES_EXTENSION: &str = "files";
+const FILES_HEADER: &str = "# vem ctags file manifest: mtime\tsize\tsha256\tpath";
+// Editor snippet setting 'tags', <environment>/vem_tags.vim or .lua, sourced from the environment's config
+const TAGS_SNIPPET: &str = "vem_tags";
+
+/// One ctags invocation, minus the output file and the sources to scan
+pub struct ctags_command_t {
+    pub program: PathBuf,
+    pub options: Vec<String>,
+    pub exclude: Vec<String>, // Patterns ctags skips, applied when scanning sources for the file manifest
+}
+
+/// Result of an incremental update
+pub struct ctags_delta_t {
+    pub tag: CTAG,
+    pub rebuilt: bool, // No usable file manifest, so everything was re-tagged
+    pub retagged: usize,
+    pub removed: usize,
+}
+
+/// File system events under watched sources, from inotify on Linux; watching stops when dropped
+pub struct ctags_watch_t {
+    watcher: notify::RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl ctags_watch_t {
+    /// Paths changed by the next event, waiting at most `timeout` (forever when None); None when it passes quietly
+    pub fn next(&mut self, timeout: Option<Duration>) -> Result<Option<Vec<PathBuf>>, vem_error_t> {
+        let event = match timeout {
+            Some(timeout) => match self.events.recv_timeout(timeout) {
+                Ok(event) => event,
+                Err(mpsc::RecvTimeoutError::Timeout) => return Ok(None),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Err(watch_stopped()),
+            },
+            None => self.events.recv().map_err(|_| watch_stopped())?,
+        };
+        let event = event.map_err(std::io::Error::other)?;
+
+        // Reading a file changes nothing, and ctags itself reads every file it tags
+        if matches!(event.kind, EventKind::Access(_)) {
+            return Ok(Some(Vec::new()));
+        }
+        // Directories created or moved in after watching started need watches of their own
+        if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))) {
+            for path in &event.paths {
+                if path.is_dir() && !matches_any(&self.exclude, path) {
+                    self.watch_tree(path)?;
+                }
+            }
+        }
+        Ok(Some(event.paths))
+    }
+
+    // Watch every directory under `root` that the exclude patterns keep, one at a time, so
+    // excluded trees such as .git or node_modules never use up inotify watches or send events.
+    // Directory symlinks are followed like scan does.
+    fn watch_tree(&mut self, root: &Path) -> Result<(), vem_error_t> {
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            // A directory removed before it is reached needs no watch
+            let (real, entries) = match fs::canonicalize(&dir).and_then(|real| Ok((real, fs::read_dir(&dir)?))) {
+                Ok(found) => found,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if !visited.insert(real) {
+                continue;
+            }
+            // inotify needs one watch per directory; running out means raising fs.inotify.max_user_watches
+            if let Err(err) = self.watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                if !dir.exists() {
+                    continue;
+                }
+                return Err(vem_error_t::CommandFailed(format!("cannot watch '{}': {}", dir.display(), err)));
+            }
+            for entry in entries {
+                let entry = entry?.path();
+                if entry.is_dir() && !matches_any(&self.exclude, &entry) {
+                    pending.push(entry);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// What the file manifest records about a source file
+struct file_state_t {
+    mtime: u128, // Nanoseconds since the epoch
+    size: u64,
+    hash: String, // SHA-256 of the contents
+}
+
+/// Ctags repository trait: tag files stored per environment
+pub trait CtagsRepository {
+    fn manifest(&self, environment: &str) -> Result<MANIFEST, vem_error_t>;
+    fn generate(
+        &self,
+        environment: &str,
+        name: &str,
+        sources: &[PathBuf],
+        command: &ctags_command_t,
+    ) -> Result<CTAG, vem_error_t>;
+    fn update(
+        &self,
+        environment: &str,
+        name: &str,
+        sources: &[PathBuf],
+        command: &ctags_command_t,
+    ) -> Result<ctags_delta_t, vem_error_t>;
+    fn generate_set(
+        &self,
+        environment: &str,
+        name: &str,
+        sources: &[PathBuf],
+        include: &[String],
+        command: &ctags_command_t,
+    ) -> Result<CTAG, vem_error_t>;
+    fn languages(&self, program: &Path) -> Result<Vec<String>, vem_error_t>;
+    fn list(&self, environment: &str) -> Result<Vec<CTAG>, vem_error_t>;
+    fn delete(&self, environment: &str, file_name: &str) -> Result<PathBuf, vem_error_t>;
+    fn clean(&self, environment: &str) -> Result<Vec<PathBuf>, vem_error_t>;
+    fn write_tags_option(
+        &self,
+        environment: &str,
+        editor: &editor_type_t,
+        tag_files: &[PathBuf],
+    ) -> Result<PathBuf, vem_error_t>;
+    fn watch(&self, sources: &[PathBuf], exclude: &[String]) -> Result<ctags_watch_t, vem_error_t>;
+}
+
+/// Ctags repository implementation with embedded config
+pub struct ctags_repository {
+    base: RepositoryConfig,
+}
+
+impl ctags_repository {
+    pub fn new(config: app_config) -> Self {
+        Self {
+            base: RepositoryConfig::new(config),
+        }
+    }
+
+    // Directory holding an environment's tag files
+    fn tag_root(&self, environment: &str) -> Result<PathBuf, vem_error_t> {
+        validate_name(environment)?;
+        let env_path = self.config().environment_root().join(environment);
+        if !env_path.is_dir() {
+            return Err(vem_error_t::EnvironmentNotFound(environment.to_string()));
+        }
+        Ok(env_path.join("ctags"))
+    }
+
+    // Tag directory for a new tag file, created on demand
+    fn prepare(&self, environment: &str, name: &str) -> Result<PathBuf, vem_error_t> {
+        validate_file_name(name)?;
+        let root = self.tag_root(environment)?;
+        fs::create_dir_all(&root)?;
+        Ok(root)
+    }
+}
+
+impl Deref for ctags_repository {
+    type Target = RepositoryConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl CtagsRepository for ctags_repository {
+    /// Load the environment's vem.toml
+    fn manifest(&self, environment: &str) -> Result<MANIFEST, vem_error_t> {
+        validate_name(environment)?;
+        let manifest_path = self.config().environment_root().join(environment).join("vem.toml");
+        if !manifest_path.is_file() {
+            return Err(vem_error_t::ConfigurationError(format!(
+                "environment '{}' has no vem.toml to read [ctags] from",
+                environment
+            )));
+        }
+        manifest_rep::new().load(&manifest_path)
+    }
+
+    /// Run ctags over the sources into <environment>/ctags/<name>.tags and record the files it covered.
+    /// Sources are passed as absolute paths so the entries resolve from wherever the tag file is read.
+    fn generate(
+        &self,
+        environment: &str,
+        name: &str,
+        sources: &[PathBuf],
+        command: &ctags_command_t,
+    ) -> Result<CTAG, vem_error_t> {
+        let root = self.prepare(environment, name)?;
+        let tag_file = root.join(tag_file_name(name));
+
+        // Scan first: a file saved while ctags runs then looks changed to the next update
+        let files = scan(sources, &command.exclude, &BTreeMap::new())?;
+
+        // Write next to the target and rename, so a failed run keeps the previous tags
+        let staging = root.join(format!(".{}.tmp", tag_file_name(name)));
+        run_ctags(command, &staging, sources)?;
+        fs::rename(&staging, &tag_file)?;
+        write_files(&root.join(files_name(name)), &files)?;
+
+        describe(name, &tag_file)
+    }
+
+    /// Re-tag only the files whose contents changed since the last run and splice their entries
+    /// into the sorted tag file; without a file manifest this is a full generate
+    fn update(
+        &self,
+        environment: &str,
+        name: &str,
+        sources: &[PathBuf],
+        command: &ctags_command_t,
+    ) -> Result<ctags_delta_t, vem_error_t> {
+        let root = self.prepare(environment, name)?;
+        let tag_file = root.join(tag_file_name(name));
+        let files_path = root.join(files_name(name));
+
+        let previous = match read_files(&files_path)? {
+            Some(previous) if tag_file.is_file() => previous,
+            _ => {
+                let tag = self.generate(environment, name, sources, command)?;
+                return Ok(ctags_delta_t { tag, rebuilt: true, retagged: 0, removed: 0 });
+            },
+        };
+
+        let files = scan(sources, &command.exclude, &previous)?;
+        let changed: Vec<&PathBuf> = files
+            .iter()
+            .filter(|(path, state)| previous.get(*path).is_none_or(|old| old.hash != state.hash))
+            .map(|(path, _)| path)
+            .collect();
+        let removed: Vec<&PathBuf> = previous.keys().filter(|path| !files.contains_key(*path)).collect();
+
+        if !changed.is_empty() || !removed.is_empty() {
+            let fresh = root.join(format!(".{}.tmp", tag_file_name(name)));
+            if !changed.is_empty() {
+                // Hand ctags the file list on disk rather than the command line, which has a length limit
+                let list = root.join(format!(".{}.list", tag_file_name(name)));
+                write_list(&list, changed.iter().copied())?;
+                let result = run_ctags(command, &fresh, [OsStr::new("-L"), list.as_os_str()]);
+                let _ = fs::remove_file(&list);
+                result?;
+            }
+
+            let replaced: BTreeSet<&[u8]> = changed
+                .iter()
+                .chain(&removed)
+                .map(|path| path.as_os_str().as_encoded_bytes())
+                .collect();
+            let spliced = splice(&tag_file, (!changed.is_empty()).then_some(fresh.as_path()), &replaced);
+            let _ = fs::remove_file(&fresh);
+            spliced?;
+        }
+        write_files(&files_path, &files)?;
+
+        Ok(ctags_delta_t {
+            tag: describe(name, &tag_file)?,
+            rebuilt: false,
+            retagged: changed.len(),
+            removed: removed.len(),
+        })
+    }
+
+    /// Tag the files of several source trees into one sorted <environment>/ctags/<name>.tags.
+    /// With include patterns, only files matching one of them are tagged.
+    fn generate_set(
+        &self,
+        environment: &str,
+        name: &str,
+        sources: &[PathBuf],
+        include: &[String],
+        command: &ctags_command_t,
+    ) -> Result<CTAG, vem_error_t> {
+        let root = self.prepare(environment, name)?;
+        let tag_file = root.join(tag_file_name(name));
+
+        // Overlapping sources scan to the same paths, so every file is listed once
+        let include = patterns(include);
+        let files = scan(sources, &command.exclude, &BTreeMap::new())?;
+        let files = files.keys().filter(|path| include.is_empty() || matches_any(&include, path));
+
+        // One ctags run over the combined file list, then sorted in case the options did not ask for it
+        let list = root.join(format!(".{}.list", tag_file_name(name)));
+        let staging = root.join(format!(".{}.tmp", tag_file_name(name)));
+        write_list(&list, files)?;
+        let result = run_ctags(command, &staging, [OsStr::new("-L"), list.as_os_str()]);
+        let _ = fs::remove_file(&list);
+        result?;
+        if let Err(err) = splice(&staging, None, &BTreeSet::new()) {
+            let _ = fs::remove_file(&staging);
+            return Err(err);
+        }
+        fs::rename(&staging, &tag_file)?;
+
+        // A set's file is not one an incremental update can splice into; drop any manifest left by a
+        // repository with the same file name so the next update of that repository rebuilds it
+        match fs::remove_file(root.join(files_name(name))) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {},
+        }
+
+        describe(name, &tag_file)
+    }
+
+    /// Languages the ctags executable knows, as printed by `ctags --list-languages`
+    fn languages(&self, program: &Path) -> Result<Vec<String>, vem_error_t> {
+        let result = Command::new(program).arg("--list-languages").output()?;
+        if !result.status.success() {
+            return Err(vem_error_t::CommandFailed(format!(
+                "'{} --list-languages' {}",
+                program.display(),
+                result.status
+            )));
+        }
+        // Parsers turned off in the ctags configuration are listed with a "[disabled]" suffix
+        Ok(String::from_utf8_lossy(&result.stdout)
+            .lines()
+            .filter(|line| !line.contains("[disabled]"))
+            .filter_map(|line| line.split_whitespace().next())
+            .map(String::from)
+            .collect())
+    }
+
+    /// Tag files present for the environment, by name
+    fn list(&self, environment: &str) -> Result<Vec<CTAG>, vem_error_t> {
+        let root = self.tag_root(environment)?;
+        let mut tags = Vec::new();
+        if !root.is_dir() {
+            return Ok(tags);
+        }
+
+        for entry in fs::read_dir(&root)? {
+            let entry = entry?;
+            let path = entry.path();
+            if !entry.file_type()?.is_file() || path.extension().and_then(|e| e.to_str()) != Some(TAG_EXTENSION) {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str())
+                && !name.starts_with('.')
+            {
+                tags.push(describe(name, &path)?);
+            }
+        }
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tags)
+    }
+
+    /// Remove one tag file by file name
+    fn delete(&self, environment: &str, file_name: &str) -> Result<PathBuf, vem_error_t> {
+        validate_file_name(file_name)?;
+        let path = self.tag_root(environment)?.join(file_name);
+        if !path.is_file() {
+            return Err(vem_error_t::TagFileNotFound(file_name.to_string()));
+        }
+        fs::remove_file(&path)?;
+
+        // The file manifest is meaningless without its tag file
+        if let Some(name) = file_name.strip_suffix(&format!(".{}", TAG_EXTENSION)) {
+            let files_path = path.with_file_name(files_name(name));
+            if files_path.is_file() {
+                fs::remove_file(files_path)?;
+            }
+        }
+        Ok(path)
+    }
+
+    /// Remove the environment's ctags directory, returning the tag files it held
+    fn clean(&self, environment: &str) -> Result<Vec<PathBuf>, vem_error_t> {
+        let root = self.tag_root(environment)?;
+        let mut removed = Vec::new();
+        if !root.is_dir() {
+            return Ok(removed);
+        }
+
+        for entry in fs::read_dir(&root)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+            if path.extension().and_then(|e| e.to_str()) == Some(TAG_EXTENSION) {
+                removed.push(path);
+            }
+        }
+        fs::remove_dir(&root)?;
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Write the snippet that puts the tag files, in order, at the front of the editor's 'tags',
+    /// and make the environment's .vimrc or init.lua source it
+    fn write_tags_option(
+        &self,
+        environment: &str,
+        editor: &editor_type_t,
+        tag_files: &[PathBuf],
+    ) -> Result<PathBuf, vem_error_t> {
+        validate_name(environment)?;
+        let env_path = self.config().environment_root().join(environment);
+        if !env_path.is_dir() {
+            return Err(vem_error_t::EnvironmentNotFound(environment.to_string()));
+        }
+
+        // 'tags' separates files with commas, so commas and spaces in a path are backslash-escaped
+        let entries: Vec<String> = tag_files
+            .iter()
+            .map(|path| path.display().to_string().replace('\\', "\\\\").replace(',', "\\,").replace(' ', "\\ "))
+            .collect();
+        let (snippet, config, source, contents) = match editor {
+            editor_type_t::VIM => {
+                let mut contents =
+                    String::from("\" Generated by vem from [ctags] in vem.toml; rewritten when tags are generated or removed\n");
+                if !entries.is_empty() {
+                    let list: Vec<String> = entries.iter().map(|entry| format!("'{}'", entry.replace('\'', "''"))).collect();
+                    contents.push_str(&format!("let s:vem_tags = [{}]\n", list.join(", ")));
+                    // Keep the rest of 'tags' (e.g. ./tags;) after them, without repeating an entry
+                    contents.push_str(
+                        "let &tags = join(s:vem_tags + filter(split(&tags, '\\\\\\@<!,'), 'index(s:vem_tags, v:val) < 0'), ',')\n",
+                    );
+                    contents.push_str("unlet s:vem_tags\n");
+                }
+                let snippet = format!("{}.vim", TAGS_SNIPPET);
+                let source = format!(
+                    concat!(
+                        "if filereadable(fnamemodify(resolve(expand('<sfile>:p')), ':h') . '/{0}')",
+                        " | execute 'source' fnameescape(fnamemodify(resolve(expand('<sfile>:p')), ':h') . '/{0}') | endif",
+                    ),
+                    snippet
+                );
+                (snippet, ".vimrc", source, contents)
+            },
+            editor_type_t::NEOVIM => {
+                let mut contents =
+                    String::from("-- Generated by vem from [ctags] in vem.toml; rewritten when tags are generated or removed\n");
+                if !entries.is_empty() {
+                    let list: Vec<String> = entries
+                        .iter()
+                        .map(|entry| format!("'{}'", entry.replace('\\', "\\\\").replace('\'', "\\'")))
+                        .collect();
+                    contents.push_str(&format!("local vem_tags = {{ {} }}\n", list.join(", ")));
+                    contents.push_str("vim.opt.tags:remove(vem_tags)\nvim.opt.tags:prepend(vem_tags)\n");
+                }
+                let snippet = format!("{}.lua", TAGS_SNIPPET);
+                let source = format!(
+                    "if vim.fn.filereadable(vim.fn.stdpath('config') .. '/{0}') == 1 then dofile(vim.fn.stdpath('config') .. '/{0}') end",
+                    snippet
+                );
+                (snippet, "init.lua", source, contents)
+            },
+        };
+
+        let snippet_path = env_path.join(&snippet);
+        fs::write(&snippet_path, contents)?;
+
+        // A hard-link mirror of this environment in `current` only has the files that existed when it was made;
+        // the config rewritten below keeps its inode, but a new snippet has to be linked in
+        let current = current_link_path();
+        let mirrored = fs::symlink_metadata(&current).is_ok_and(|metadata| metadata.is_dir())
+            && fs::read_to_string(current_state_path()).is_ok_and(|state| state.trim() == environment);
+        if mirrored {
+            let mirror = current.join(&snippet);
+            if fs::symlink_metadata(&mirror).is_ok() {
+                fs::remove_file(&mirror)?;
+            }
+            fs::hard_link(&snippet_path, &mirror)?;
+        }
+
+        // Source it last, so it sees 'tags' as the template's config left it
+        let config_path = env_path.join(config);
+        if config_path.is_file() {
+            let mut config = fs::read_to_string(&config_path)?;
+            if !config.contains(&snippet) {
+                if !config.is_empty() && !config.ends_with('\n') {
+                    config.push('\n');
+                }
+                config.push_str(&source);
+                config.push('\n');
+                fs::write(&config_path, config)?;
+            }
+        }
+        Ok(snippet_path)
+    }
+
+    /// Watch the sources recursively for changes
+    fn watch(&self, sources: &[PathBuf], exclude: &[String]) -> Result<ctags_watch_t, vem_error_t> {
+        let (sender, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(sender).map_err(std::io::Error::other)?;
+        let mut watch = ctags_watch_t {
+            watcher,
+            events,
+            exclude: patterns(exclude),
+        };
+        for source in sources {
+            watch.watch_tree(source)?;
+        }
+        Ok(watch)
+    }
+}
+
+/// Tag file name for a repository key
+pub fn tag_file_name(name: &str) -> String {
+    format!("{}.{}", name, TAG_EXTENSION)
+}
+
+/// Resolve a configured path: `~` is the home directory, relative paths start at `base`
+pub fn resolve_path(path: &str, base: &Path) -> PathBuf {
+    let expanded = expand_home(path);
+    if expanded.is_absolute() { expanded } else { base.join(expanded) }
+}
+
+/// Whether a path under `source` is skipped by the exclude patterns, on its own name or a parent directory's
+pub fn excluded(path: &Path, source: &Path, exclude: &[String]) -> bool {
+    let exclude = patterns(exclude);
+    path.ancestors()
+        .take_while(|ancestor| *ancestor != source)
+        .any(|ancestor| matches_any(&exclude, ancestor))
+}
+
+// Tag files live directly under <environment>/ctags; reject anything that would escape it
+fn validate_file_name(name: &str) -> Result<(), vem_error_t> {
+    if name.is_empty() || name.starts_with('.') || name.contains('/') || name.contains('\\') {
+        return Err(vem_error_t::InvalidArgument(format!("invalid tag file name '{}'", name)));
+    }
+    Ok(())
+}
+
+// File manifest name for a repository key
+fn files_name(name: &str) -> String {
+    format!("{}.{}", name, FILES_EXTENSION)
+}
+
+// Run ctags writing to `output`; `inputs` are the sources or a `-L <list>` pair
+fn run_ctags<I, S>(command: &ctags_command_t, output: &Path, inputs: I) -> Result<(), vem_error_t>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let result = Command::new(&command.program)
+        .args(&command.options)
+        .arg("-f")
+        .arg(output)
+        .args(inputs)
+        .output();
+    let result = match result {
+        Ok(result) => result,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(vem_error_t::CommandFailed(format!(
+                "ctags executable '{}' not found in PATH",
+                command.program.display()
+            )));
+        },
+        Err(err) => return Err(err.into()),
+    };
+    if !result.status.success() {
+        let _ = fs::remove_file(output);
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(vem_error_t::CommandFailed(format!(
+            "'{}' {}: {}",
+            command.program.display(),
+            result.status,
+            stderr.trim()
+        )));
+    }
+    Ok(())
+}
+
+fn watch_stopped() -> vem_error_t {
+    vem_error_t::CommandFailed("file watcher stopped".to_string())
+}
+
+// Write a `ctags -L` file list, one path per line
+fn write_list<'a>(path: &Path, files: impl IntoIterator<Item = &'a PathBuf>) -> Result<(), vem_error_t> {
+    let mut contents = Vec::new();
+    for file in files {
+        contents.extend_from_slice(file.as_os_str().as_encoded_bytes());
+        contents.push(b'\n');
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+// Glob patterns; one that does not parse matches itself literally
+fn patterns(patterns: &[String]) -> Vec<glob::Pattern> {
+    patterns
+        .iter()
+        .map(|pattern| glob::Pattern::new(pattern).unwrap_or_else(|_| glob::Pattern::new(&glob::Pattern::escape(pattern)).unwrap()))
+        .collect()
+}
+
+// Whether a pattern matches the file name or the whole path
+fn matches_any(patterns: &[glob::Pattern], path: &Path) -> bool {
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    patterns.iter().any(|pattern| pattern.matches(&name) || pattern.matches_path(path))
+}
+
+// Walk the sources like `ctags --recurse` would, hashing files whose mtime or size differ from `previous`
+fn scan(
+    sources: &[PathBuf],
+    exclude: &[String],
+    previous: &BTreeMap<PathBuf, file_state_t>,
+) -> Result<BTreeMap<PathBuf, file_state_t>, vem_error_t> {
+    let exclude = patterns(exclude);
+
+    let mut files = BTreeMap::new();
+    let mut visited = BTreeSet::new();
+    let mut pending: Vec<PathBuf> = sources.to_vec();
+    while let Some(path) = pending.pop() {
+        // Directory symlinks are followed like ctags' default `--links=yes`; each real
+        // directory is walked once, so a link cycle cannot trap the walk. Anything removed
+        // mid-walk is left out, as a deleted file
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue; // Dangling link or removed
+        };
+        if metadata.is_dir() {
+            let (real, entries) = match fs::canonicalize(&path).and_then(|real| Ok((real, fs::read_dir(&path)?))) {
+                Ok(found) => found,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if !visited.insert(real) {
+                continue;
+            }
+            for entry in entries {
+                let entry = entry?.path();
+                if !matches_any(&exclude, &entry) {
+                    pending.push(entry);
+                }
+            }
+            continue;
+        }
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let mtime = metadata.modified()?.duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+        let size = metadata.len();
+        let hash = match previous.get(&path) {
+            Some(old) if old.mtime == mtime && old.size == size => old.hash.clone(),
+            _ => match hash_file(&path) {
+                Ok(hash) => hash,
+                Err(vem_error_t::FileSystemError(err)) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            },
+        };
+        files.insert(path, file_state_t { mtime, size, hash });
+    }
+    Ok(files)
+}
+
+// SHA-256 of a file as lowercase hex
+fn hash_file(path: &Path) -> Result<String, vem_error_t> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// Read a file manifest; None when there is none or it cannot be trusted
+fn read_files(path: &Path) -> Result<Option<BTreeMap<PathBuf, file_state_t>>, vem_error_t> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(path)?;
+    let mut files = BTreeMap::new();
+    for line in contents.lines().filter(|line| !line.starts_with('#')) {
+        let mut fields = line.splitn(4, '\t');
+        let (Some(mtime), Some(size), Some(hash), Some(file)) = (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Ok(None);
+        };
+        let (Ok(mtime), Ok(size)) = (mtime.parse(), size.parse()) else {
+            return Ok(None);
+        };
+        files.insert(PathBuf::from(file), file_state_t { mtime, size, hash: hash.to_string() });
+    }
+    Ok(Some(files))
+}
+
+fn write_files(path: &Path, files: &BTreeMap<PathBuf, file_state_t>) -> Result<(), vem_error_t> {
+    let staging = path.with_file_name(format!(".{}.tmp", path.file_name().unwrap_or_default().to_string_lossy()));
+    let mut out = std::io::BufWriter::new(fs::File::create(&staging)?);
+    writeln!(out, "{}", FILES_HEADER)?;
+    for (file, state) in files {
+        writeln!(out, "{}\t{}\t{}\t{}", state.mtime, state.size, state.hash, file.display())?;
+    }
+    out.flush()?;
+    drop(out);
+    fs::rename(&staging, path)?;
+    Ok(())
+}
+
+// Replace the entries of `replaced` files in a sorted tag file with those from `fresh`, keeping it sorted.
+// With nothing to replace this just sorts the file.
+fn splice(tag_file: &Path, fresh: Option<&Path>, replaced: &BTreeSet<&[u8]>) -> Result<(), vem_error_t> {
+    let contents = fs::read(tag_file)?;
+    let mut header = Vec::new();
+    let mut entries = Vec::new();
+    for line in contents.split(|b| *b == b'\n').filter(|line| !line.is_empty()) {
+        if line.starts_with(b"!_") {
+            header.push(line.to_vec());
+        } else if !line.split(|b| *b == b'\t').nth(1).is_some_and(|file| replaced.contains(file)) {
+            entries.push(line.to_vec());
+        }
+    }
+    if let Some(fresh) = fresh {
+        let contents = fs::read(fresh)?;
+        for line in contents.split(|b| *b == b'\n').filter(|line| !line.is_empty() && !line.starts_with(b"!_")) {
+            entries.push(line.to_vec());
+        }
+    }
+
+    // Entries are written sorted whatever the input declared
+    for line in &mut header {
+        if line.starts_with(b"!_TAG_FILE_SORTED\t0") {
+            line[b"!_TAG_FILE_SORTED\t".len()] = b'1';
+        }
+    }
+
+    // Match the order ctags declared: byte order, or case-folded with --sort=foldcase
+    if header.iter().any(|line| line.starts_with(b"!_TAG_FILE_SORTED\t2")) {
+        entries.sort_by_cached_key(|line| line.to_ascii_lowercase());
+    } else {
+        entries.sort();
+    }
+    entries.dedup();
+
+    let staging = tag_file.with_file_name(format!(".{}.splice", tag_file.file_name().unwrap_or_default().to_string_lossy()));
+    let mut out = std::io::BufWriter::new(fs::File::create(&staging)?);
+    for line in header.iter().chain(&entries) {
+        out.write_all(line)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    drop(out);
+    fs::rename(&staging, tag_file)?;
+    Ok(())
+}
+
+// Size and modification time of a tag file; the status is filled in against vem.toml by the caller
+fn describe(name: &str, tag_file: &Path) -> Result<CTAG, vem_error_t> {
+    let metadata = fs::metadata(tag_file)?;
+    Ok(CTAG {
+        name: name.to_string(),
+        repository: name.to_string(),
+        tag_file: tag_file.to_path_buf(),
+        last_updated: metadata.modified()?.into(),
+        size: metadata.len(),
+        status: ctag_status_t::ACTIVE,
+    })
+}
+
+/// Factory function to create ctags repository
+pub fn new(config: app_config) -> impl CtagsRepository {
+    ctags_repository::new(config)
+}
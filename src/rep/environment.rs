@@ -1,9 +1,19 @@
 use std::fs;
+use std::path::Path;
 use std::ops::Deref;
 
-use crate::cnf::application::{app_config, current_link_path};
+use crate::cnf::application::{
+    app_config,
+    current_link_path,
+    current_state_path,
+    session_environment,
+    symlink_mode_t,
+    trash_root,
+};
 use crate::util::error::vem_error_t;
 use crate::ent::model::environment::ENVIRONMENT;
+use crate::ent::model::manifest::editor_type_t;
+use crate::ent::model::template::TEMPLATE;
 
 /// Base configuration holder - similar to Go's embedded struct
 pub struct RepositoryConfig {
@@ -20,15 +30,30 @@ impl RepositoryConfig {
     }
 }
 
+// Trash directory names: sortable UTC timestamps, with a ".N" suffix on collisions
+const TRASH_ID_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
 /// Environment repository trait
 pub trait EnvironmentRepository {
-    fn create(&self, name: &str, description: Option<String>) -> (ENVIRONMENT, bool);
-    fn list(&self) -> Vec<ENVIRONMENT>;
-    fn get(&self, name: &str) -> ENVIRONMENT;
-    fn update(&self, name: &str, description: Option<String>) -> (ENVIRONMENT, bool);
-    fn delete(&self, name: &str) -> bool;
-    fn get_current(&self) -> ENVIRONMENT;
-    fn set_current(&self, name: &str) -> bool;
+    fn create(
+        &self,
+        name: &str,
+        description: Option<String>,
+        tags: Vec<String>,
+        editor_type: &editor_type_t,
+        template: Option<&TEMPLATE>,
+    ) -> Result<ENVIRONMENT, vem_error_t>;
+    fn list(&self) -> Result<(Vec<ENVIRONMENT>, Vec<String>), vem_error_t>;
+    fn get(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t>;
+    fn update(&self, name: &str, description: Option<String>, tags: Vec<String>) -> Result<ENVIRONMENT, vem_error_t>;
+    fn delete(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t>;
+    fn undelete(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t>;
+    fn get_current(&self) -> Result<ENVIRONMENT, vem_error_t>;
+    fn get_linked(&self) -> Result<ENVIRONMENT, vem_error_t>;
+    fn set_current(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t>;
+    fn unset_current(&self) -> Result<(), vem_error_t>;
+    fn touch(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t>;
+    fn disk_usage(&self, name: &str) -> Result<u64, vem_error_t>;
 }
 
 /// Environment repository implementation with embedded config
@@ -54,49 +79,41 @@ impl Deref for environment_repository {
 }
 
 impl EnvironmentRepository for environment_repository {
-    /// Create a new environment
-    fn create(&self, name: &str, description: Option<String>) -> (ENVIRONMENT, bool) {
-        // Validate environment name
-        if name.is_empty() || name.contains('/') || name.contains('\\') {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
-        }
+    /// Create a new environment, optionally populated from a template
+    fn create(
+        &self,
+        name: &str,
+        description: Option<String>,
+        tags: Vec<String>,
+        editor_type: &editor_type_t,
+        template: Option<&TEMPLATE>,
+    ) -> Result<ENVIRONMENT, vem_error_t> {
+        validate_name(name)?;
+        let tags = normalize_tags(tags)?;
 
         // Access config through Deref (like Go's embedded field access)
         let env_path = self.config().environment_root().join(name);
 
         // Check if environment already exists
         if env_path.exists() {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
+            return Err(vem_error_t::EnvironmentAlreadyExists(name.to_string()));
         }
 
         // Create environment directory structure
-        if fs::create_dir_all(&env_path).is_err() {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
-        }
+        fs::create_dir_all(&env_path)?;
+
+        // Description defaults to the template's [environment] table; explicit tags extend the template's
+        let (description, tags) = match template {
+            Some(template) => {
+                let mut merged = template.manifest.environment.tags.clone();
+                merged.extend(tags);
+                (
+                    description.or_else(|| template.manifest.environment.description.clone()),
+                    normalize_tags(merged)?,
+                )
+            },
+            None => (description, tags),
+        };
 
         let env = ENVIRONMENT {
             name: name.to_string(),
@@ -104,136 +121,65 @@ impl EnvironmentRepository for environment_repository {
             created: chrono::Utc::now(),
             update: chrono::Utc::now(),
             last_used: None,
-            tags: Vec::new(),
+            tags,
         };
 
-        // Create .vimrc file
-        let vimrc_path = env_path.join(".vimrc");
-        if !vimrc_path.exists()
-            && fs::write(&vimrc_path, format!("\" VEM Environment: {}\n", name)).is_err()
-        {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
-        }
-
-        // Create .vim directory structure
-        let vim_dir = env_path.join(".vim");
-        if fs::create_dir_all(&vim_dir).is_err()
-            || fs::create_dir_all(vim_dir.join("autoload")).is_err()
-            || fs::create_dir_all(vim_dir.join("bundle")).is_err()
-            || fs::create_dir_all(vim_dir.join("colors")).is_err()
-            || fs::create_dir_all(vim_dir.join("plugin")).is_err()
-        {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
-        }
-
-        // Save metadata
-        if self.save_metadata(name, &env).is_err() {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
+        // Remove the half-built directory so a retry is not blocked by "already exists"
+        if let Err(err) = self.populate(&env_path, &env, editor_type, template) {
+            let _ = fs::remove_dir_all(&env_path);
+            return Err(err);
         }
 
-        (env, true)
+        Ok(env)
     }
 
-    /// List all environments
-    fn list(&self) -> Vec<ENVIRONMENT> {
+    /// List all environments; those that cannot be read come back as warnings
+    fn list(&self) -> Result<(Vec<ENVIRONMENT>, Vec<String>), vem_error_t> {
         let mut environments = Vec::new();
+        let mut skipped = Vec::new();
 
         // Access config through Deref (like Go's embedded field)
         if !self.config().environment_root().exists() {
-            return environments;
+            return Ok((environments, skipped));
         }
 
-        let Ok(entries) = fs::read_dir(self.config().environment_root()) else {
-            return environments;
-        };
-
-        for entry in entries {
-            let Ok(entry) = entry else { continue };
-            let path = entry.path();
+        for entry in fs::read_dir(self.config().environment_root())? {
+            let path = entry?.path();
 
             if path.is_dir()
                 && let Some(name) = path.file_name().and_then(|n| n.to_str())
+                && !name.starts_with('.')
             {
-                let env = self.get(name);
-                if !env.name.is_empty() {
-                    environments.push(env);
+                // One unreadable meta.toml must not hide every other environment
+                match self.get(name) {
+                    Ok(environment) => environments.push(environment),
+                    Err(err) => skipped.push(format!("skipped environment '{}': {}", name, err)),
                 }
             }
         }
 
         // Sort by name
         environments.sort_by(|a, b| a.name.cmp(&b.name));
-        environments
+        skipped.sort();
+        Ok((environments, skipped))
     }
 
     /// Get an environment by name
-    fn get(&self, name: &str) -> ENVIRONMENT {
-        let env_path = self.config().environment_root().join(name);
+    fn get(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t> {
+        validate_name(name)?;
 
-        if !env_path.exists() {
-            return ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
+        let env_path = self.config().environment_root().join(name);
+        if !env_path.is_dir() {
+            return Err(vem_error_t::EnvironmentNotFound(name.to_string()));
         }
 
-        let Ok(env) = self.load_metadata(name) else {
-            return ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-        };
-
-        env
+        self.load_metadata(name)
     }
 
-    /// Update an environment's metadata
-    fn update(&self, name: &str, description: Option<String>) -> (ENVIRONMENT, bool) {
-        let env = self.get(name);
-        
-        if env.name.is_empty() {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
-        }
+    /// Replace an environment's description and tags
+    fn update(&self, name: &str, description: Option<String>, tags: Vec<String>) -> Result<ENVIRONMENT, vem_error_t> {
+        let env = self.get(name)?;
+        let tags = normalize_tags(tags)?;
 
         let updated_env = ENVIRONMENT {
             name: name.to_string(),
@@ -241,112 +187,132 @@ impl EnvironmentRepository for environment_repository {
             created: env.created,
             update: chrono::Utc::now(),
             last_used: env.last_used,
-            tags: env.tags,
+            tags,
         };
-        
-        if self.save_metadata(name, &updated_env).is_err() {
-            let default_env = ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
-            return (default_env, false);
-        }
+        self.save_metadata(name, &updated_env)?;
 
-        (updated_env, true)
+        Ok(updated_env)
     }
 
     /// Delete an environment
-    fn delete(&self, name: &str) -> bool {
-        let env_path = self.config().environment_root().join(name);
-
-        if !env_path.exists() {
-            return false;
-        }
+    fn delete(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t> {
+        let env = self.get(name)?;
 
         // Check if it's the current environment
-        let current = self.get_current();
-        if !current.name.is_empty() && current.name == name {
-            return false;
+        match self.get_current() {
+            Ok(current) if current.name == name => {
+                return Err(vem_error_t::EnvironmentInUse(name.to_string()));
+            },
+            Ok(_) | Err(vem_error_t::NoCurrentEnvironment) | Err(vem_error_t::EnvironmentNotFound(_)) => {},
+            Err(err) => return Err(err),
         }
 
-        fs::remove_dir_all(&env_path).is_ok()
+        // Move into the trash rather than deleting, so `undelete env` can bring it back
+        let env_trash = trash_root().join(name);
+        fs::create_dir_all(&env_trash)?;
+        let stamp = chrono::Utc::now().format(TRASH_ID_FORMAT).to_string();
+        let mut id = stamp.clone();
+        let mut suffix = 1;
+        while env_trash.join(&id).exists() {
+            suffix += 1;
+            id = format!("{}.{}", stamp, suffix);
+        }
+        move_tree(&self.config().environment_root().join(name), &env_trash.join(id))?;
+        Ok(env)
     }
 
-    /// Get the current environment
-    fn get_current(&self) -> ENVIRONMENT {
-        let current_link = current_link_path();
-
-        if !current_link.exists() {
-            return ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
+    /// Restore the most recently trashed copy of an environment
+    fn undelete(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t> {
+        validate_name(name)?;
+        let env_path = self.config().environment_root().join(name);
+        if env_path.exists() {
+            return Err(vem_error_t::EnvironmentAlreadyExists(name.to_string()));
         }
 
-        let Ok(target) = fs::read_link(&current_link) else {
-            return ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            };
+        let env_trash = trash_root().join(name);
+        let mut entries = Vec::new();
+        if env_trash.is_dir() {
+            for entry in fs::read_dir(&env_trash)? {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() {
+                    entries.push(entry.path());
+                }
+            }
+        }
+        // Ids are timestamps, so the newest sorts last (".N" collision suffixes sort after their stamp)
+        entries.sort();
+        let Some(latest) = entries.pop() else {
+            return Err(vem_error_t::EnvironmentNotFound(name.to_string()));
         };
 
-        if let Some(name) = target.file_name().and_then(|n| n.to_str()) {
-            self.get(name)
-        } else {
-            ENVIRONMENT {
-                name: String::new(),
-                description: None,
-                created: chrono::Utc::now(),
-                update: chrono::Utc::now(),
-                last_used: None,
-                tags: Vec::new(),
-            }
+        move_tree(&latest, &env_path)?;
+        if entries.is_empty() {
+            let _ = fs::remove_dir(&env_trash);
         }
+        self.get(name)
     }
 
-    /// Set the current environment
-    fn set_current(&self, name: &str) -> bool {
-        let env = self.get(name);
-        
-        if env.name.is_empty() {
-            return false;
+    /// Get the current environment: this session's VEM_ENV, else the global `current` link
+    fn get_current(&self) -> Result<ENVIRONMENT, vem_error_t> {
+        match session_environment() {
+            Some(name) => self.get(&name),
+            None => self.get_linked(),
         }
+    }
 
+    /// Get the environment `current` points at, whichever mode it was created in
+    fn get_linked(&self) -> Result<ENVIRONMENT, vem_error_t> {
         let current_link = current_link_path();
-        let env_path = self.config().environment_root().join(name);
 
-        // Remove existing symlink if it exists
-        if current_link.exists()
-            && fs::remove_file(&current_link).is_err()
-        {
-            return false;
+        // A missing or dangling link means no environment is active
+        if !current_link.exists() {
+            return Err(vem_error_t::NoCurrentEnvironment);
         }
 
-        // Create symlink to the environment
-        #[cfg(unix)]
-        {
-            if std::os::unix::fs::symlink(&env_path, &current_link).is_err() {
-                return false;
+        // Hard-link mirror: the name lives in the state file
+        if !fs::symlink_metadata(&current_link)?.file_type().is_symlink() {
+            let state_path = current_state_path();
+            if !state_path.is_file() {
+                return Err(vem_error_t::NoCurrentEnvironment);
             }
+            let name = fs::read_to_string(&state_path)?;
+            return self.get(name.trim());
         }
 
-        #[cfg(windows)]
-        {
-            if std::os::windows::fs::symlink_dir(&env_path, &current_link).is_err() {
-                return false;
-            }
+        let target = fs::read_link(&current_link)?;
+        let Some(name) = target.file_name().and_then(|n| n.to_str()) else {
+            return Err(vem_error_t::NoCurrentEnvironment);
+        };
+
+        self.get(name)
+    }
+
+    /// Set the current environment
+    fn set_current(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t> {
+        let env = self.get(name)?;
+
+        let current_link = current_link_path();
+        let env_path = self.config().environment_root().join(name);
+
+        self.clear_current()?;
+
+        match self.config().symlink_mode {
+            symlink_mode_t::SYMBOLIC => {
+                // Create symlink to the environment
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(&env_path, &current_link)?;
+
+                #[cfg(windows)]
+                std::os::windows::fs::symlink_dir(&env_path, &current_link)?;
+            },
+            symlink_mode_t::HARD => {
+                // Mirror the tree with hard links for tools that refuse to follow symlinks
+                if let Err(err) = mirror_tree(&env_path, &current_link) {
+                    let _ = fs::remove_dir_all(&current_link);
+                    return Err(err);
+                }
+                fs::write(current_state_path(), format!("{}\n", name))?;
+            },
         }
 
         // Update last_used timestamp
@@ -358,16 +324,317 @@ impl EnvironmentRepository for environment_repository {
             last_used: Some(chrono::Utc::now()),
             tags: env.tags,
         };
-        if self.save_metadata(name, &updated_env).is_err() {
-            return false;
+        self.save_metadata(name, &updated_env)?;
+
+        Ok(updated_env)
+    }
+
+    /// Remove `current` so no environment is active
+    fn unset_current(&self) -> Result<(), vem_error_t> {
+        self.clear_current()
+    }
+
+    /// Record that an environment was just used without switching to it
+    fn touch(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t> {
+        let env = self.get(name)?;
+        let updated_env = ENVIRONMENT {
+            last_used: Some(chrono::Utc::now()),
+            ..env
+        };
+        self.save_metadata(name, &updated_env)?;
+
+        Ok(updated_env)
+    }
+
+    /// Total size in bytes of an environment directory, without following symlinks
+    fn disk_usage(&self, name: &str) -> Result<u64, vem_error_t> {
+        validate_name(name)?;
+        directory_size(&self.config().environment_root().join(name))
+    }
+}
+
+// Recreate `source` at `dest`: directories are created, files hard-linked, symlinks copied
+fn mirror_tree(source: &Path, dest: &Path) -> Result<(), vem_error_t> {
+    fs::create_dir(dest)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            mirror_tree(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+
+            #[cfg(windows)]
+            fs::copy(entry.path(), &target)?;
+        } else {
+            fs::hard_link(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+// Carry what was written into a hard-link mirror back into `dest`: new files, files replaced
+// rather than edited in place (no longer a hard link of the environment's copy), new directories
+// and symlinks. Files removed from the mirror stay in the environment.
+fn sync_mirror(mirror: &Path, dest: &Path) -> Result<(), vem_error_t> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(mirror)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            sync_mirror(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            let link = fs::read_link(entry.path())?;
+            if fs::read_link(&target).ok().as_ref() == Some(&link) {
+                continue;
+            }
+            remove_file_at(&target)?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(link, &target)?;
+
+            #[cfg(windows)]
+            fs::copy(entry.path(), &target)?;
+        } else if !same_file(&entry.path(), &target) {
+            remove_file_at(&target)?;
+            // Both trees live under VEM_HOME, so this is normally a rename on one file system
+            if fs::rename(entry.path(), &target).is_err() {
+                fs::copy(entry.path(), &target)?;
+            }
         }
+    }
+    Ok(())
+}
+
+// Remove a file or symlink that is about to be replaced; a directory in the way is an error
+fn remove_file_at(path: &Path) -> Result<(), vem_error_t> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => Err(vem_error_t::ConfigurationError(format!(
+            "{} is a directory in the environment but not in current",
+            path.display()
+        ))),
+        Ok(_) => Ok(fs::remove_file(path)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+// Whether two paths are hard links of one file
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
 
-        true
+// Without inode numbers, files that still match in size and mtime count as the same
+#[cfg(not(unix))]
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => b.is_file() && a.len() == b.len() && a.modified().ok() == b.modified().ok(),
+        _ => false,
     }
 }
 
+// Recursively copy `source` to `dest`, recreating symlinks rather than following them
+pub fn copy_tree(source: &Path, dest: &Path) -> Result<(), vem_error_t> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_tree(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+
+            #[cfg(windows)]
+            fs::copy(entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+// Move a directory, falling back to copy-and-delete across file systems
+fn move_tree(source: &Path, dest: &Path) -> Result<(), vem_error_t> {
+    if fs::rename(source, dest).is_ok() {
+        return Ok(());
+    }
+    if let Err(err) = copy_tree(source, dest) {
+        let _ = fs::remove_dir_all(dest);
+        return Err(err);
+    }
+    fs::remove_dir_all(source)?;
+    Ok(())
+}
+
+// Sum file sizes below `path`; symlinks count as their own size
+pub fn directory_size(path: &Path) -> Result<u64, vem_error_t> {
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = fs::symlink_metadata(entry.path())?;
+        if metadata.is_dir() {
+            total += directory_size(&entry.path())?;
+        } else {
+            total += metadata.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Validate an environment name before it is used as a directory name
+/// (dot-prefixed names are reserved for vem's own working directories)
+pub fn validate_name(name: &str) -> Result<(), vem_error_t> {
+    if name.is_empty() || name.starts_with('.') || name.contains('/') || name.contains('\\') {
+        return Err(vem_error_t::InvalidEnvironmentName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Validate tags and drop duplicates, keeping first-seen order
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, vem_error_t> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        if tag.is_empty() || tag.contains(char::is_whitespace) || tag.contains(',') {
+            return Err(vem_error_t::InvalidTag(tag));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    Ok(normalized)
+}
+
 // Private helper methods
 impl environment_repository {
+    /// Write the initial files of a freshly created environment
+    fn populate(
+        &self,
+        env_path: &Path,
+        env: &ENVIRONMENT,
+        editor_type: &editor_type_t,
+        template: Option<&TEMPLATE>,
+    ) -> Result<(), vem_error_t> {
+        // Install template files verbatim (vem.toml, vimrc, init.lua, lua/, ...)
+        if let Some(template) = template {
+            for file in &template.files {
+                if file.path == Path::new("meta.toml") {
+                    continue;
+                }
+                let target = env_path.join(&file.path);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&target, &file.contents)?;
+            }
+        }
+
+        let config_file = template.and_then(|t| t.manifest.editor.config_file.as_deref());
+
+        match editor_type {
+            editor_type_t::VIM => {
+                // Create .vimrc file, sourcing the template's config file when there is one
+                let vimrc_path = env_path.join(".vimrc");
+                let mut vimrc = format!("\" VEM Environment: {}\n", env.name);
+                if let Some(config_file) = config_file.filter(|f| *f != ".vimrc") {
+                    vimrc.push_str(&format!(
+                        "execute 'source' fnameescape(fnamemodify(resolve(expand('<sfile>:p')), ':h') . '/{}')\n",
+                        config_file
+                    ));
+                }
+                if !vimrc_path.exists() {
+                    fs::write(&vimrc_path, vimrc)?;
+                }
+
+                // Create .vim directory structure
+                let vim_dir = env_path.join(".vim");
+                for dir in ["autoload", "bundle", "colors", "plugin"] {
+                    fs::create_dir_all(vim_dir.join(dir))?;
+                }
+            },
+            editor_type_t::NEOVIM => {
+                // Neovim reads init.lua and lua/ from its config directory, which is the environment itself
+                let init_path = env_path.join("init.lua");
+                if !init_path.exists() {
+                    let mut init = format!("-- VEM Environment: {}\n", env.name);
+                    if let Some(config_file) = config_file.filter(|f| *f != "init.lua") {
+                        init.push_str(&format!("dofile(vim.fn.stdpath('config') .. '/{}')\n", config_file));
+                    }
+                    fs::write(&init_path, init)?;
+                }
+                fs::create_dir_all(env_path.join("lua"))?;
+
+                // Record the editor type so activation and launching know this is a Neovim environment
+                let manifest_path = env_path.join("vem.toml");
+                if !manifest_path.exists() {
+                    // Built with toml_edit so the name is quoted and escaped as TOML requires
+                    let mut manifest = toml_edit::DocumentMut::new();
+                    manifest["environment"] = toml_edit::table();
+                    manifest["environment"]["name"] = toml_edit::value(env.name.as_str());
+                    manifest["environment"]["type"] = toml_edit::value("neovim");
+                    manifest["editor"] = toml_edit::table();
+                    manifest["editor"]["type"] = toml_edit::value("neovim");
+                    manifest["editor"]["config_file"] = toml_edit::value("init.lua");
+                    fs::write(&manifest_path, manifest.to_string())?;
+                }
+            },
+        }
+
+        // Save metadata
+        self.save_metadata(&env.name, env)
+    }
+
+    /// Remove the current link or hard-link mirror, and the state file naming it
+    fn clear_current(&self) -> Result<(), vem_error_t> {
+        let current_link = current_link_path();
+        let state_path = current_state_path();
+
+        if let Ok(metadata) = fs::symlink_metadata(&current_link) {
+            if metadata.file_type().is_symlink() || metadata.is_file() {
+                fs::remove_file(&current_link)?;
+            } else if state_path.is_file() {
+                // Only a mirror vem created (recorded by the state file) is safe to remove, and only
+                // once whatever was saved into it rather than through its hard links is kept
+                let name = fs::read_to_string(&state_path)?;
+                let env_path = self.config().environment_root().join(name.trim());
+                if !env_path.is_dir() {
+                    return Err(vem_error_t::ConfigurationError(format!(
+                        "{} mirrors environment '{}', which no longer exists; move it away before switching",
+                        current_link.display(),
+                        name.trim()
+                    )));
+                }
+                sync_mirror(&current_link, &env_path).map_err(|err| {
+                    vem_error_t::ConfigurationError(format!(
+                        "cannot save changes under {} back to environment '{}', so it was left in place: {}",
+                        current_link.display(),
+                        name.trim(),
+                        err
+                    ))
+                })?;
+                fs::remove_dir_all(&current_link)?;
+            } else {
+                return Err(vem_error_t::ConfigurationError(format!(
+                    "{} is a directory not managed by vem; move it away before switching",
+                    current_link.display()
+                )));
+            }
+        }
+
+        if state_path.is_file() {
+            fs::remove_file(&state_path)?;
+        }
+        Ok(())
+    }
+
     /// Save environment metadata to meta.toml
     fn save_metadata(&self, name: &str, env: &ENVIRONMENT) -> Result<(), vem_error_t> {
         let env_path = self.config().environment_root().join(name);
@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+
+use crate::ent::model::local::LOCAL;
+use crate::rep::environment::validate_name;
+use crate::util::error::vem_error_t;
+
+// Per-directory environment file, like .python-version
+const LOCAL_FILE: &str = ".vem-env";
+
+/// Local (per-directory) environment repository trait
+pub trait LocalRepository {
+    fn find(&self, start: &Path) -> Result<Option<LOCAL>, vem_error_t>;
+    fn write(&self, dir: &Path, name: &str) -> Result<LOCAL, vem_error_t>;
+    fn remove(&self, dir: &Path) -> Result<Option<LOCAL>, vem_error_t>;
+}
+
+/// Local repository reading and writing `.vem-env` files
+pub struct local_repository;
+
+impl LocalRepository for local_repository {
+    /// Find the nearest `.vem-env` in `start` or one of its parents
+    fn find(&self, start: &Path) -> Result<Option<LOCAL>, vem_error_t> {
+        for dir in start.ancestors() {
+            if let Some(local) = read(&dir.join(LOCAL_FILE))? {
+                return Ok(Some(local));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Pin `dir` to an environment
+    fn write(&self, dir: &Path, name: &str) -> Result<LOCAL, vem_error_t> {
+        validate_name(name)?;
+        let path = dir.join(LOCAL_FILE);
+        fs::write(&path, format!("{}\n", name))?;
+
+        Ok(LOCAL {
+            environment: name.to_string(),
+            path,
+        })
+    }
+
+    /// Remove the `.vem-env` in `dir` (not its parents); None when there was none
+    fn remove(&self, dir: &Path) -> Result<Option<LOCAL>, vem_error_t> {
+        let path = dir.join(LOCAL_FILE);
+        let local = read(&path)?;
+        if path.is_file() {
+            fs::remove_file(&path)?;
+        }
+        Ok(local)
+    }
+}
+
+// The environment named by the first non-blank, non-comment line; None when the file is absent or empty
+fn read(path: &Path) -> Result<Option<LOCAL>, vem_error_t> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(path)?;
+    let name = contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'));
+    Ok(name.map(|name| LOCAL {
+        environment: name.to_string(),
+        path: path.to_path_buf(),
+    }))
+}
+
+/// Factory function to create local repository
+pub fn new() -> impl LocalRepository {
+    local_repository
+}
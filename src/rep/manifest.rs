@@ -0,0 +1,838 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+use crate::ent::model::manifest::{
+    AI_TOOL, AI_TOOLS, COMPATIBILITY, COMPLETION, CTAGS, CTAGS_COMMANDS, CTAGS_GLOBAL, CTAGS_GUTENTAGS,
+    CTAGS_REPOSITORY, CTAGS_TAG_SET, EDITOR, GIT, KEYMAP, KEYMAPS, LSP, MANIFEST, MANIFEST_ENVIRONMENT,
+    PACKAGES, PERFORMANCE, PLUGIN_MANAGER_CONFIG, PLUGIN_MANAGERS, PLUGIN_PACKAGE, PLUGINS, REQUIREMENTS,
+    SYSTEM_PACKAGE, THEME, TREESITTER, editor_type_t,
+};
+use crate::util::error::vem_error_t;
+
+/// Manifest repository trait
+pub trait ManifestRepository {
+    fn load(&self, path: &Path) -> Result<MANIFEST, vem_error_t>;
+    fn parse(&self, source: &str, origin: &str) -> Result<MANIFEST, vem_error_t>;
+}
+
+/// Manifest repository implementation reading vem.toml files
+pub struct manifest_repository;
+
+impl ManifestRepository for manifest_repository {
+    /// Load and parse a vem.toml file
+    fn load(&self, path: &Path) -> Result<MANIFEST, vem_error_t> {
+        let source = fs::read_to_string(path)?;
+        self.parse(&source, &path.display().to_string())
+    }
+
+    /// Parse vem.toml content; `origin` names the source in diagnostics
+    fn parse(&self, source: &str, origin: &str) -> Result<MANIFEST, vem_error_t> {
+        let mut reader = manifest_reader {
+            source,
+            origin,
+            warnings: Vec::new(),
+        };
+
+        let document = ImDocument::parse(source)
+            .map_err(|e| reader.error(e.span(), e.message().trim_end()))?;
+        reader.read_manifest(document.as_table())
+    }
+}
+
+// Table-like section together with the span used for diagnostics
+type section_t<'a> = (&'a dyn TableLike, Option<Range<usize>>);
+
+const ROOT_KEYS: &[&str] = &[
+    "environment", "editor", "features", "plugins", "plugin_managers", "packages", "ctags", "theme", "keymaps", "lsp",
+    "ai_tools", "treesitter", "completion", "git", "performance", "compatibility", "requirements",
+];
+
+// Walks a parsed document, collecting unknown-key warnings and reporting type errors with locations
+struct manifest_reader<'a> {
+    source: &'a str,
+    origin: &'a str,
+    warnings: Vec<String>,
+}
+
+impl manifest_reader<'_> {
+    fn read_manifest(&mut self, root: &dyn TableLike) -> Result<MANIFEST, vem_error_t> {
+        self.warn_unknown(root, "", ROOT_KEYS);
+
+        let environment = self.section(root, "environment", "")?;
+        let editor = self.section(root, "editor", "")?;
+        let features = self.section(root, "features", "")?;
+        let plugins = self.section(root, "plugins", "")?;
+        let plugin_managers = self.section(root, "plugin_managers", "")?;
+        let packages = self.section(root, "packages", "")?;
+        let ctags = self.section(root, "ctags", "")?;
+        let theme = self.section(root, "theme", "")?;
+        let keymaps = self.section(root, "keymaps", "")?;
+        let lsp = self.section(root, "lsp", "")?;
+        let ai_tools = self.section(root, "ai_tools", "")?;
+        let treesitter = self.section(root, "treesitter", "")?;
+        let completion = self.section(root, "completion", "")?;
+        let git = self.section(root, "git", "")?;
+        let performance = self.section(root, "performance", "")?;
+        let compatibility = self.section(root, "compatibility", "")?;
+        let requirements = self.section(root, "requirements", "")?;
+
+        let environment = self.read_environment(environment)?;
+        let editor = self.read_editor(editor, &environment)?;
+
+        Ok(MANIFEST {
+            environment,
+            editor,
+            features: self.bool_map(features, "features")?,
+            plugins: self.read_plugins(plugins)?,
+            plugin_managers: self.read_plugin_managers(plugin_managers)?,
+            packages: self.read_packages(packages)?,
+            ctags: self.read_ctags(ctags)?,
+            theme: self.read_theme(theme)?,
+            keymaps: self.read_keymaps(keymaps)?,
+            lsp: self.read_lsp(lsp)?,
+            ai_tools: self.read_ai_tools(ai_tools)?,
+            treesitter: self.read_treesitter(treesitter)?,
+            completion: self.read_completion(completion)?,
+            git: self.read_git(git)?,
+            performance: self.read_performance(performance)?,
+            compatibility: self.read_compatibility(compatibility)?,
+            requirements: self.read_requirements(requirements)?,
+            warnings: std::mem::take(&mut self.warnings),
+        })
+    }
+
+    fn read_environment(&mut self, section: Option<section_t>) -> Result<MANIFEST_ENVIRONMENT, vem_error_t> {
+        let prefix = "environment";
+        let Some((t, _)) = section else {
+            return Ok(MANIFEST_ENVIRONMENT {
+                name: None,
+                description: None,
+                env_type: None,
+                version: None,
+                author: None,
+                tags: Vec::new(),
+            });
+        };
+        self.warn_unknown(t, prefix, &["name", "description", "type", "version", "author", "tags"]);
+
+        Ok(MANIFEST_ENVIRONMENT {
+            name: self.string(t, "name", prefix)?,
+            description: self.string(t, "description", prefix)?,
+            env_type: self.editor_type(t, "type", prefix)?,
+            version: self.string(t, "version", prefix)?,
+            author: self.string(t, "author", prefix)?,
+            tags: self.strings(t, "tags", prefix)?,
+        })
+    }
+
+    fn read_editor(
+        &mut self,
+        section: Option<section_t>,
+        environment: &MANIFEST_ENVIRONMENT,
+    ) -> Result<EDITOR, vem_error_t> {
+        let prefix = "editor";
+        // Fall back to [environment].type when [editor] does not say
+        let fallback = match environment.env_type {
+            Some(editor_type_t::NEOVIM) => editor_type_t::NEOVIM,
+            _ => editor_type_t::VIM,
+        };
+        let Some((t, _)) = section else {
+            return Ok(EDITOR {
+                editor_type: fallback,
+                config_file: None,
+                global_configs: Vec::new(),
+            });
+        };
+        self.warn_unknown(t, prefix, &["type", "config_file", "global_configs"]);
+
+        Ok(EDITOR {
+            editor_type: self.editor_type(t, "type", prefix)?.unwrap_or(fallback),
+            config_file: self.string(t, "config_file", prefix)?,
+            global_configs: self.strings(t, "global_configs", prefix)?,
+        })
+    }
+
+    fn read_plugins(&mut self, section: Option<section_t>) -> Result<PLUGINS, vem_error_t> {
+        let prefix = "plugins";
+        let t = self.known(section, prefix, &["enabled", "manager", "auto_install"]);
+
+        Ok(PLUGINS {
+            enabled: self.boolean(t, "enabled", prefix)?.unwrap_or(false),
+            manager: self.string_opt(t, "manager", prefix)?,
+            auto_install: self.boolean(t, "auto_install", prefix)?.unwrap_or(false),
+        })
+    }
+
+    fn read_plugin_managers(&mut self, section: Option<section_t>) -> Result<PLUGIN_MANAGERS, vem_error_t> {
+        let prefix = "plugin_managers";
+        let mut selected = BTreeMap::new();
+        let mut configs = BTreeMap::new();
+        let Some((t, _)) = section else {
+            return Ok(PLUGIN_MANAGERS { selected, configs });
+        };
+
+        for (key, item) in t.iter() {
+            if key == "configs" {
+                continue;
+            }
+            match item.as_bool() {
+                Some(value) => {
+                    selected.insert(key.to_string(), value);
+                },
+                None => return Err(self.type_error(t, key, item, prefix, "a boolean")),
+            }
+        }
+
+        if let Some((configs_table, _)) = self.section(t, "configs", prefix)? {
+            for (name, item) in configs_table.iter() {
+                let path = format!("{}.configs.{}", prefix, name);
+                let Some(config) = item.as_table_like() else {
+                    return Err(self.type_error(configs_table, name, item, &path, "a table"));
+                };
+                configs.insert(name.to_string(), self.read_plugin_manager_config(config, &path)?);
+            }
+        }
+
+        Ok(PLUGIN_MANAGERS { selected, configs })
+    }
+
+    fn read_plugin_manager_config(
+        &mut self,
+        t: &dyn TableLike,
+        prefix: &str,
+    ) -> Result<PLUGIN_MANAGER_CONFIG, vem_error_t> {
+        self.warn_unknown(t, prefix, &[
+            "url", "install_path", "config_setup", "config_line", "config_block_start", "config_block_end",
+            "install_command", "update_command", "clean_command", "packages",
+        ]);
+
+        let mut packages = Vec::new();
+        for (index, (package, span)) in self.table_array(t, "packages", prefix)?.into_iter().enumerate() {
+            let path = format!("{}.packages[{}]", prefix, index);
+            self.warn_unknown(package, &path, &[
+                "name", "description", "category", "dependencies", "build", "config", "priority",
+            ]);
+            packages.push(PLUGIN_PACKAGE {
+                name: self.required_string(package, "name", &path, span)?,
+                description: self.string(package, "description", &path)?,
+                category: self.string(package, "category", &path)?,
+                dependencies: self.strings(package, "dependencies", &path)?,
+                build: self.string(package, "build", &path)?,
+                config: self.string(package, "config", &path)?,
+                priority: self.unsigned(package, "priority", &path)?,
+            });
+        }
+
+        Ok(PLUGIN_MANAGER_CONFIG {
+            url: self.string(t, "url", prefix)?,
+            install_path: self.string(t, "install_path", prefix)?,
+            config_setup: self.string(t, "config_setup", prefix)?,
+            config_line: self.string(t, "config_line", prefix)?,
+            config_block_start: self.string(t, "config_block_start", prefix)?,
+            config_block_end: self.string(t, "config_block_end", prefix)?,
+            install_command: self.string(t, "install_command", prefix)?,
+            update_command: self.string(t, "update_command", prefix)?,
+            clean_command: self.string(t, "clean_command", prefix)?,
+            packages,
+        })
+    }
+
+    fn read_packages(&mut self, section: Option<section_t>) -> Result<PACKAGES, vem_error_t> {
+        let prefix = "packages";
+        let mut system = Vec::new();
+        let Some((t, _)) = section else {
+            return Ok(PACKAGES { system });
+        };
+        self.warn_unknown(t, prefix, &["system"]);
+
+        for (index, (package, span)) in self.table_array(t, "system", prefix)?.into_iter().enumerate() {
+            let path = format!("{}.system[{}]", prefix, index);
+            self.warn_unknown(package, &path, &["name", "package_managers"]);
+            let managers = self.section(package, "package_managers", &path)?;
+            system.push(SYSTEM_PACKAGE {
+                name: self.required_string(package, "name", &path, span)?,
+                package_managers: self.string_map(managers, &format!("{}.package_managers", path))?,
+            });
+        }
+
+        Ok(PACKAGES { system })
+    }
+
+    fn read_ctags(&mut self, section: Option<section_t>) -> Result<CTAGS, vem_error_t> {
+        let prefix = "ctags";
+        let t = self.known(section, prefix, &[
+            "enabled", "executable", "global_config_file", "auto_generate", "update_on_save", "ai_enhanced", "global",
+            "repositories", "tags", "commands", "gutentags", "ai_integration", "lsp_integration",
+        ]);
+
+        let global = match t {
+            Some(t) => self.section(t, "global", prefix)?,
+            None => None,
+        };
+        let global_prefix = "ctags.global";
+        let global_table = self.known(global, global_prefix, &["languages", "exclude_patterns", "custom_options"]);
+
+        let mut repositories = BTreeMap::new();
+        if let Some(t) = t
+            && let Some((repos, _)) = self.section(t, "repositories", prefix)?
+        {
+            for (name, item) in repos.iter() {
+                let path = format!("ctags.repositories.{}", name);
+                let Some(repo) = item.as_table_like() else {
+                    return Err(self.type_error(repos, name, item, &path, "a table"));
+                };
+                repositories.insert(name.to_string(), self.read_ctags_repository(repo, name, &path)?);
+            }
+        }
+
+        let mut tags = BTreeMap::new();
+        if let Some(t) = t
+            && let Some((sets, _)) = self.section(t, "tags", prefix)?
+        {
+            for (name, item) in sets.iter() {
+                let path = format!("ctags.tags.{}", name);
+                let Some(set) = item.as_table_like() else {
+                    return Err(self.type_error(sets, name, item, &path, "a table"));
+                };
+                tags.insert(name.to_string(), self.read_ctags_tag_set(set, name, &path)?);
+            }
+        }
+
+        let commands = match t {
+            Some(t) => self.section(t, "commands", prefix)?,
+            None => None,
+        };
+        let gutentags = match t {
+            Some(t) => self.section(t, "gutentags", prefix)?,
+            None => None,
+        };
+        let ai_integration = match t {
+            Some(t) => self.section(t, "ai_integration", prefix)?,
+            None => None,
+        };
+        let lsp_integration = match t {
+            Some(t) => self.section(t, "lsp_integration", prefix)?,
+            None => None,
+        };
+
+        Ok(CTAGS {
+            enabled: self.boolean(t, "enabled", prefix)?.unwrap_or(false),
+            executable: self.string_opt(t, "executable", prefix)?.unwrap_or_else(|| "ctags".to_string()),
+            global_config_file: self.string_opt(t, "global_config_file", prefix)?,
+            auto_generate: self.boolean(t, "auto_generate", prefix)?.unwrap_or(false),
+            update_on_save: self.boolean(t, "update_on_save", prefix)?.unwrap_or(false),
+            ai_enhanced: self.boolean(t, "ai_enhanced", prefix)?.unwrap_or(false),
+            global: CTAGS_GLOBAL {
+                languages: self.strings_opt(global_table, "languages", global_prefix)?,
+                exclude_patterns: self.strings_opt(global_table, "exclude_patterns", global_prefix)?,
+                custom_options: self.strings_opt(global_table, "custom_options", global_prefix)?,
+            },
+            repositories,
+            tags,
+            commands: self.read_ctags_commands(commands)?,
+            gutentags: self.read_ctags_gutentags(gutentags)?,
+            ai_integration: self.string_map(ai_integration, "ctags.ai_integration")?,
+            lsp_integration: self.string_map(lsp_integration, "ctags.lsp_integration")?,
+        })
+    }
+
+    fn read_ctags_repository(
+        &mut self,
+        t: &dyn TableLike,
+        key: &str,
+        prefix: &str,
+    ) -> Result<CTAGS_REPOSITORY, vem_error_t> {
+        self.warn_unknown(t, prefix, &[
+            "name", "description", "path", "remote_url", "branch", "enabled", "auto_sync", "priority",
+        ]);
+
+        Ok(CTAGS_REPOSITORY {
+            name: self.string(t, "name", prefix)?.unwrap_or_else(|| key.to_string()),
+            description: self.string(t, "description", prefix)?,
+            path: self.string(t, "path", prefix)?.unwrap_or_else(|| ".".to_string()),
+            remote_url: self.string(t, "remote_url", prefix)?.filter(|s| !s.is_empty()),
+            branch: self.string(t, "branch", prefix)?.filter(|s| !s.is_empty()),
+            enabled: self.boolean(Some(t), "enabled", prefix)?.unwrap_or(true),
+            auto_sync: self.boolean(Some(t), "auto_sync", prefix)?.unwrap_or(false),
+            priority: self.unsigned(t, "priority", prefix)?.unwrap_or(0),
+        })
+    }
+
+    fn read_ctags_tag_set(&mut self, t: &dyn TableLike, key: &str, prefix: &str) -> Result<CTAGS_TAG_SET, vem_error_t> {
+        self.warn_unknown(t, prefix, &[
+            "name", "description", "tag_file", "repositories", "source_dirs", "languages", "include_patterns",
+            "exclude_patterns", "custom_options", "auto_generate", "project_root_markers", "ai_context", "ai_priority",
+        ]);
+
+        Ok(CTAGS_TAG_SET {
+            name: self.string(t, "name", prefix)?.unwrap_or_else(|| key.to_string()),
+            description: self.string(t, "description", prefix)?,
+            tag_file: self.string(t, "tag_file", prefix)?.unwrap_or_else(|| format!("{}_tags", key)),
+            repositories: self.strings(t, "repositories", prefix)?,
+            source_dirs: self.strings(t, "source_dirs", prefix)?,
+            languages: self.strings(t, "languages", prefix)?,
+            include_patterns: self.strings(t, "include_patterns", prefix)?,
+            exclude_patterns: self.strings(t, "exclude_patterns", prefix)?,
+            custom_options: self.strings(t, "custom_options", prefix)?,
+            auto_generate: self.boolean(Some(t), "auto_generate", prefix)?.unwrap_or(false),
+            project_root_markers: self.strings(t, "project_root_markers", prefix)?,
+            ai_context: self.boolean(Some(t), "ai_context", prefix)?.unwrap_or(false),
+            ai_priority: self.string(t, "ai_priority", prefix)?,
+        })
+    }
+
+    fn read_ctags_commands(&mut self, section: Option<section_t>) -> Result<CTAGS_COMMANDS, vem_error_t> {
+        let prefix = "ctags.commands";
+        let t = self.known(section, prefix, &[
+            "generate_command", "generate_options", "update_command", "update_options", "delete_command",
+            "delete_options", "list_format", "list_fields", "clean_backup", "clean_confirm", "ai_enhance",
+            "ai_context_update", "ai_context_cleanup", "ai_context_reset",
+        ]);
+
+        Ok(CTAGS_COMMANDS {
+            generate_command: self.string_opt(t, "generate_command", prefix)?,
+            generate_options: self.strings_opt(t, "generate_options", prefix)?,
+            update_command: self.string_opt(t, "update_command", prefix)?,
+            update_options: self.strings_opt(t, "update_options", prefix)?,
+            delete_command: self.string_opt(t, "delete_command", prefix)?,
+            delete_options: self.strings_opt(t, "delete_options", prefix)?,
+            list_format: self.string_opt(t, "list_format", prefix)?,
+            list_fields: self.strings_opt(t, "list_fields", prefix)?,
+            clean_backup: self.boolean(t, "clean_backup", prefix)?.unwrap_or(false),
+            clean_confirm: self.boolean(t, "clean_confirm", prefix)?.unwrap_or(true),
+            ai_enhance: self.boolean(t, "ai_enhance", prefix)?.unwrap_or(false),
+            ai_context_update: self.boolean(t, "ai_context_update", prefix)?.unwrap_or(false),
+            ai_context_cleanup: self.boolean(t, "ai_context_cleanup", prefix)?.unwrap_or(false),
+            ai_context_reset: self.boolean(t, "ai_context_reset", prefix)?.unwrap_or(false),
+        })
+    }
+
+    fn read_ctags_gutentags(&mut self, section: Option<section_t>) -> Result<CTAGS_GUTENTAGS, vem_error_t> {
+        let prefix = "ctags.gutentags";
+        let t = self.known(section, prefix, &["enabled", "project_root_markers", "cache_dir", "exclude_dirs"]);
+
+        Ok(CTAGS_GUTENTAGS {
+            enabled: self.boolean(t, "enabled", prefix)?.unwrap_or(false),
+            project_root_markers: self.strings_opt(t, "project_root_markers", prefix)?,
+            cache_dir: self.string_opt(t, "cache_dir", prefix)?,
+            exclude_dirs: self.strings_opt(t, "exclude_dirs", prefix)?,
+        })
+    }
+
+    fn read_theme(&mut self, section: Option<section_t>) -> Result<THEME, vem_error_t> {
+        let prefix = "theme";
+        let t = self.known(section, prefix, &["name", "variant", "background", "airline_theme"]);
+
+        Ok(THEME {
+            name: self.string_opt(t, "name", prefix)?,
+            variant: self.string_opt(t, "variant", prefix)?,
+            background: self.string_opt(t, "background", prefix)?,
+            airline_theme: self.string_opt(t, "airline_theme", prefix)?,
+        })
+    }
+
+    fn read_keymaps(&mut self, section: Option<section_t>) -> Result<KEYMAPS, vem_error_t> {
+        let prefix = "keymaps";
+        let t = self.known(section, prefix, &["leader", "custom_maps"]);
+
+        let mut custom_maps = Vec::new();
+        if let Some(t) = t {
+            for (index, (map, span)) in self.table_array(t, "custom_maps", prefix)?.into_iter().enumerate() {
+                let path = format!("{}.custom_maps[{}]", prefix, index);
+                self.warn_unknown(map, &path, &["key", "action", "mode", "type"]);
+                custom_maps.push(KEYMAP {
+                    key: self.required_string(map, "key", &path, span.clone())?,
+                    action: self.required_string(map, "action", &path, span)?,
+                    mode: self.string(map, "mode", &path)?,
+                    map_type: self.string(map, "type", &path)?,
+                });
+            }
+        }
+
+        Ok(KEYMAPS {
+            leader: self.string_opt(t, "leader", prefix)?,
+            custom_maps,
+        })
+    }
+
+    fn read_lsp(&mut self, section: Option<section_t>) -> Result<LSP, vem_error_t> {
+        let prefix = "lsp";
+        let t = self.known(section, prefix, &["enabled", "provider", "auto_install", "languages"]);
+
+        Ok(LSP {
+            enabled: self.boolean(t, "enabled", prefix)?.unwrap_or(false),
+            provider: self.string_opt(t, "provider", prefix)?,
+            auto_install: self.boolean(t, "auto_install", prefix)?.unwrap_or(false),
+            languages: self.strings_opt(t, "languages", prefix)?,
+        })
+    }
+
+    fn read_ai_tools(&mut self, section: Option<section_t>) -> Result<AI_TOOLS, vem_error_t> {
+        let prefix = "ai_tools";
+        let mut tools = BTreeMap::new();
+        let Some((t, _)) = section else {
+            return Ok(AI_TOOLS {
+                enabled: false,
+                tools,
+            });
+        };
+
+        for (name, item) in t.iter() {
+            if name == "enabled" {
+                continue;
+            }
+            let path = format!("{}.{}", prefix, name);
+            let Some(tool) = item.as_table_like() else {
+                self.warn_key(t, name, &path);
+                continue;
+            };
+            let enabled = self.boolean(Some(tool), "enabled", &path)?.unwrap_or(false);
+            let mut options = self.string_map(Some((tool, item.span())), &path)?;
+            options.remove("enabled");
+            tools.insert(name.to_string(), AI_TOOL { enabled, options });
+        }
+
+        Ok(AI_TOOLS {
+            enabled: self.boolean(Some(t), "enabled", prefix)?.unwrap_or(false),
+            tools,
+        })
+    }
+
+    fn read_treesitter(&mut self, section: Option<section_t>) -> Result<TREESITTER, vem_error_t> {
+        let prefix = "treesitter";
+        let t = self.known(section, prefix, &["enabled", "auto_install", "languages"]);
+
+        Ok(TREESITTER {
+            enabled: self.boolean(t, "enabled", prefix)?.unwrap_or(false),
+            auto_install: self.boolean(t, "auto_install", prefix)?.unwrap_or(false),
+            languages: self.strings_opt(t, "languages", prefix)?,
+        })
+    }
+
+    fn read_completion(&mut self, section: Option<section_t>) -> Result<COMPLETION, vem_error_t> {
+        let prefix = "completion";
+        let t = self.known(section, prefix, &["enabled", "sources", "snippet_engine"]);
+
+        Ok(COMPLETION {
+            enabled: self.boolean(t, "enabled", prefix)?.unwrap_or(false),
+            sources: self.strings_opt(t, "sources", prefix)?,
+            snippet_engine: self.string_opt(t, "snippet_engine", prefix)?,
+        })
+    }
+
+    fn read_git(&mut self, section: Option<section_t>) -> Result<GIT, vem_error_t> {
+        let prefix = "git";
+        let t = self.known(section, prefix, &["enabled", "show_changes", "signs", "fugitive_maps"]);
+
+        Ok(GIT {
+            enabled: self.boolean(t, "enabled", prefix)?.unwrap_or(false),
+            show_changes: self.boolean(t, "show_changes", prefix)?.unwrap_or(false),
+            signs: self.boolean(t, "signs", prefix)?.unwrap_or(false),
+            fugitive_maps: self.boolean(t, "fugitive_maps", prefix)?.unwrap_or(false),
+        })
+    }
+
+    fn read_performance(&mut self, section: Option<section_t>) -> Result<PERFORMANCE, vem_error_t> {
+        let prefix = "performance";
+        let t = self.known(section, prefix, &[
+            "swap_files", "backup_files", "backup_dir", "undo_levels", "update_time",
+        ]);
+
+        Ok(PERFORMANCE {
+            swap_files: self.boolean(t, "swap_files", prefix)?.unwrap_or(false),
+            backup_files: self.boolean(t, "backup_files", prefix)?.unwrap_or(false),
+            backup_dir: self.string_opt(t, "backup_dir", prefix)?,
+            undo_levels: match t {
+                Some(t) => self.unsigned(t, "undo_levels", prefix)?,
+                None => None,
+            },
+            update_time: match t {
+                Some(t) => self.unsigned(t, "update_time", prefix)?,
+                None => None,
+            },
+        })
+    }
+
+    fn read_compatibility(&mut self, section: Option<section_t>) -> Result<COMPATIBILITY, vem_error_t> {
+        let prefix = "compatibility";
+        let t = self.known(section, prefix, &["vim_version", "neovim_version", "neovim_compatible"]);
+
+        Ok(COMPATIBILITY {
+            vim_version: self.string_opt(t, "vim_version", prefix)?,
+            neovim_version: self.string_opt(t, "neovim_version", prefix)?,
+            neovim_compatible: self.boolean(t, "neovim_compatible", prefix)?.unwrap_or(false),
+        })
+    }
+
+    fn read_requirements(&mut self, section: Option<section_t>) -> Result<REQUIREMENTS, vem_error_t> {
+        let prefix = "requirements";
+        let t = self.known(section, prefix, &["environment_vars", "external_tools"]);
+
+        Ok(REQUIREMENTS {
+            environment_vars: self.strings_opt(t, "environment_vars", prefix)?,
+            external_tools: self.strings_opt(t, "external_tools", prefix)?,
+        })
+    }
+}
+
+// Primitive accessors
+impl manifest_reader<'_> {
+    /// Convert a byte span into a `file:line:column` location
+    fn location(&self, span: Option<Range<usize>>) -> String {
+        let Some(span) = span else {
+            return self.origin.to_string();
+        };
+        let offset = span.start.min(self.source.len());
+        let before = &self.source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+        format!("{}:{}:{}", self.origin, line, column)
+    }
+
+    fn error(&self, span: Option<Range<usize>>, message: &str) -> vem_error_t {
+        vem_error_t::ConfigurationError(format!("{}: {}", self.location(span), message))
+    }
+
+    fn type_error(&self, table: &dyn TableLike, key: &str, item: &Item, prefix: &str, expected: &str) -> vem_error_t {
+        let span = item.span().or_else(|| table.key(key).and_then(|k| k.span()));
+        self.error(span, &format!("expected {} for `{}`, found {}", expected, join(prefix, key), item.type_name()))
+    }
+
+    fn warn_key(&mut self, table: &dyn TableLike, key: &str, path: &str) {
+        let span = table.key(key).and_then(|k| k.span());
+        let warning = format!("{}: unknown key `{}`", self.location(span), path);
+        self.warnings.push(warning);
+    }
+
+    fn warn_unknown(&mut self, table: &dyn TableLike, prefix: &str, known: &[&str]) {
+        let unknown: Vec<String> = table
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .filter(|key| !known.contains(&key.as_str()))
+            .collect();
+        for key in unknown {
+            self.warn_key(table, &key, &join(prefix, &key));
+        }
+    }
+
+    // Unwrap an optional section after checking its keys
+    fn known<'t>(&mut self, section: Option<section_t<'t>>, prefix: &str, keys: &[&str]) -> Option<&'t dyn TableLike> {
+        let (t, _) = section?;
+        self.warn_unknown(t, prefix, keys);
+        Some(t)
+    }
+
+    fn section<'t>(
+        &self,
+        table: &'t dyn TableLike,
+        key: &str,
+        prefix: &str,
+    ) -> Result<Option<section_t<'t>>, vem_error_t> {
+        match table.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_table_like() {
+                Some(t) => Ok(Some((t, item.span()))),
+                None => Err(self.type_error(table, key, item, prefix, "a table")),
+            },
+        }
+    }
+
+    fn string(&self, table: &dyn TableLike, key: &str, prefix: &str) -> Result<Option<String>, vem_error_t> {
+        match table.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_str() {
+                Some(s) => Ok(Some(s.to_string())),
+                None => Err(self.type_error(table, key, item, prefix, "a string")),
+            },
+        }
+    }
+
+    fn string_opt(
+        &self,
+        table: Option<&dyn TableLike>,
+        key: &str,
+        prefix: &str,
+    ) -> Result<Option<String>, vem_error_t> {
+        match table {
+            Some(t) => self.string(t, key, prefix),
+            None => Ok(None),
+        }
+    }
+
+    fn required_string(
+        &self,
+        table: &dyn TableLike,
+        key: &str,
+        prefix: &str,
+        span: Option<Range<usize>>,
+    ) -> Result<String, vem_error_t> {
+        match self.string(table, key, prefix)? {
+            Some(s) => Ok(s),
+            None => Err(self.error(span, &format!("missing required key `{}`", join(prefix, key)))),
+        }
+    }
+
+    fn boolean(&self, table: Option<&dyn TableLike>, key: &str, prefix: &str) -> Result<Option<bool>, vem_error_t> {
+        let Some(table) = table else {
+            return Ok(None);
+        };
+        match table.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_bool() {
+                Some(b) => Ok(Some(b)),
+                None => Err(self.type_error(table, key, item, prefix, "a boolean")),
+            },
+        }
+    }
+
+    fn unsigned(&self, table: &dyn TableLike, key: &str, prefix: &str) -> Result<Option<u32>, vem_error_t> {
+        match table.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_integer().and_then(|i| u32::try_from(i).ok()) {
+                Some(n) => Ok(Some(n)),
+                None => Err(self.type_error(table, key, item, prefix, "a non-negative integer")),
+            },
+        }
+    }
+
+    fn strings(&self, table: &dyn TableLike, key: &str, prefix: &str) -> Result<Vec<String>, vem_error_t> {
+        let Some(item) = table.get(key) else {
+            return Ok(Vec::new());
+        };
+        let Some(array) = item.as_array() else {
+            return Err(self.type_error(table, key, item, prefix, "an array of strings"));
+        };
+
+        let mut values = Vec::new();
+        for value in array.iter() {
+            match value.as_str() {
+                Some(s) => values.push(s.to_string()),
+                None => {
+                    return Err(self.error(
+                        value.span(),
+                        &format!("expected strings in `{}`, found {}", join(prefix, key), value.type_name()),
+                    ));
+                },
+            }
+        }
+        Ok(values)
+    }
+
+    fn strings_opt(&self, table: Option<&dyn TableLike>, key: &str, prefix: &str) -> Result<Vec<String>, vem_error_t> {
+        match table {
+            Some(t) => self.strings(t, key, prefix),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn editor_type(
+        &self,
+        table: &dyn TableLike,
+        key: &str,
+        prefix: &str,
+    ) -> Result<Option<editor_type_t>, vem_error_t> {
+        let Some(value) = self.string(table, key, prefix)? else {
+            return Ok(None);
+        };
+        match value.as_str() {
+            "vim" => Ok(Some(editor_type_t::VIM)),
+            "neovim" | "nvim" => Ok(Some(editor_type_t::NEOVIM)),
+            other => {
+                let span = table.get(key).and_then(|item| item.span());
+                let message =
+                    format!("unknown editor type `{}` for `{}` (expected vim or neovim)", other, join(prefix, key));
+                Err(self.error(span, &message))
+            },
+        }
+    }
+
+    // Collect `{ ... }` entries from an array of inline tables or an array of tables
+    fn table_array<'t>(
+        &self,
+        table: &'t dyn TableLike,
+        key: &str,
+        prefix: &str,
+    ) -> Result<Vec<section_t<'t>>, vem_error_t> {
+        let Some(item) = table.get(key) else {
+            return Ok(Vec::new());
+        };
+
+        if let Some(array) = item.as_array_of_tables() {
+            return Ok(array.iter().map(|t| (t as &dyn TableLike, t.span())).collect());
+        }
+
+        let Some(array) = item.as_array() else {
+            return Err(self.type_error(table, key, item, prefix, "an array of tables"));
+        };
+        let mut entries = Vec::new();
+        for value in array.iter() {
+            match value.as_inline_table() {
+                Some(t) => entries.push((t as &dyn TableLike, value.span())),
+                None => {
+                    return Err(self.error(
+                        value.span(),
+                        &format!("expected inline tables in `{}`, found {}", join(prefix, key), value.type_name()),
+                    ));
+                },
+            }
+        }
+        Ok(entries)
+    }
+
+    fn bool_map(&self, section: Option<section_t>, prefix: &str) -> Result<BTreeMap<String, bool>, vem_error_t> {
+        let mut map = BTreeMap::new();
+        let Some((t, _)) = section else {
+            return Ok(map);
+        };
+        for (key, item) in t.iter() {
+            match item.as_bool() {
+                Some(b) => {
+                    map.insert(key.to_string(), b);
+                },
+                None => return Err(self.type_error(t, key, item, prefix, "a boolean")),
+            }
+        }
+        Ok(map)
+    }
+
+    // Free-form section: scalar and array values rendered as TOML text
+    fn string_map(&self, section: Option<section_t>, prefix: &str) -> Result<BTreeMap<String, String>, vem_error_t> {
+        let mut map = BTreeMap::new();
+        let Some((t, _)) = section else {
+            return Ok(map);
+        };
+        for (key, item) in t.iter() {
+            match item.as_value() {
+                Some(value) => {
+                    map.insert(key.to_string(), render_value(value));
+                },
+                None => return Err(self.type_error(t, key, item, prefix, "a value")),
+            }
+        }
+        Ok(map)
+    }
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn render_value(value: &Value) -> String {
+    match value.as_str() {
+        Some(s) => s.to_string(),
+        None => value.clone().decorated("", "").to_string(),
+    }
+}
+
+/// Factory function to create manifest repository
+pub fn new() -> impl ManifestRepository {
+    manifest_repository
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cnf::application::bin_root;
+use crate::util::error::vem_error_t;
+
+/// Shim repository trait
+pub trait ShimRepository {
+    fn install(&self, programs: &[&str], vem: &Path) -> Result<Vec<PathBuf>, vem_error_t>;
+    fn find_real(&self, program: &str) -> Result<PathBuf, vem_error_t>;
+    fn bin_root(&self) -> &Path;
+}
+
+/// Shim repository writing launcher scripts into VEM_HOME/bin
+pub struct shim_repository {
+    bin_root: PathBuf,
+}
+
+impl shim_repository {
+    pub fn new(bin_root: PathBuf) -> Self {
+        Self { bin_root }
+    }
+}
+
+impl ShimRepository for shim_repository {
+    /// Write one shim per program that hands the call back to `vem shim exec`
+    fn install(&self, programs: &[&str], vem: &Path) -> Result<Vec<PathBuf>, vem_error_t> {
+        fs::create_dir_all(&self.bin_root)?;
+
+        let mut shims = Vec::new();
+        for program in programs {
+            #[cfg(unix)]
+            let shim = {
+                use std::os::unix::fs::PermissionsExt;
+
+                let path = self.bin_root.join(program);
+                let vem = vem.display().to_string().replace('\'', "'\\''");
+                fs::write(
+                    &path,
+                    format!("#!/bin/sh\n# vem shim: runs {0} with the active vem environment\nexec '{1}' shim exec {0} \"$@\"\n", program, vem),
+                )?;
+                fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+                path
+            };
+
+            #[cfg(windows)]
+            let shim = {
+                let path = self.bin_root.join(format!("{}.cmd", program));
+                fs::write(&path, format!("@echo off\r\n\"{}\" shim exec {} %*\r\n", vem.display(), program))?;
+                path
+            };
+
+            shims.push(shim);
+        }
+        Ok(shims)
+    }
+
+    /// Locate the real editor on PATH, skipping the shim directory
+    fn find_real(&self, program: &str) -> Result<PathBuf, vem_error_t> {
+        let shim_dir = fs::canonicalize(&self.bin_root).unwrap_or_else(|_| self.bin_root.clone());
+        let path = std::env::var_os("PATH").unwrap_or_default();
+
+        for dir in std::env::split_paths(&path) {
+            if dir.as_os_str().is_empty() || fs::canonicalize(&dir).unwrap_or_else(|_| dir.clone()) == shim_dir {
+                continue;
+            }
+            let candidate = dir.join(program);
+            if is_executable(&candidate) {
+                return Ok(candidate);
+            }
+        }
+
+        Err(vem_error_t::FileSystemError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no '{}' found on PATH outside {}", program, self.bin_root.display()),
+        )))
+    }
+
+    fn bin_root(&self) -> &Path {
+        &self.bin_root
+    }
+}
+
+// Whether `path` is a file we could exec
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = fs::metadata(path) else {
+        return false;
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+/// Factory function to create shim repository
+pub fn new() -> impl ShimRepository {
+    shim_repository::new(bin_root())
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use include_dir::{Dir, include_dir};
+
+use crate::cnf::application::{get_vem_home, template_root};
+use crate::ent::model::template::{TEMPLATE, TEMPLATE_FILE};
+use crate::rep::manifest::{self as manifest_rep, ManifestRepository};
+use crate::util::error::vem_error_t;
+
+// Stock templates and shared global configs compiled into the binary
+static BUNDLED_ENVS: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/etc/.vem/envs");
+static BUNDLED_GLOBAL: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/etc/.vem/global");
+
+/// Template repository trait
+pub trait TemplateRepository {
+    fn list(&self) -> Result<Vec<TEMPLATE>, vem_error_t>;
+    fn get(&self, name: &str) -> Result<TEMPLATE, vem_error_t>;
+    fn extract(&self, name: &str, dest: &Path) -> Result<TEMPLATE, vem_error_t>;
+    fn install_global(&self) -> Result<(), vem_error_t>;
+}
+
+/// Template repository: user template directory first, then the bundled templates
+pub struct template_repository {
+    user_root: PathBuf,
+    global_root: PathBuf,
+}
+
+impl template_repository {
+    pub fn new(user_root: PathBuf, global_root: PathBuf) -> Self {
+        Self { user_root, global_root }
+    }
+}
+
+impl TemplateRepository for template_repository {
+    /// List all templates; user templates shadow bundled ones of the same name
+    fn list(&self) -> Result<Vec<TEMPLATE>, vem_error_t> {
+        let mut names: Vec<String> = BUNDLED_ENVS
+            .dirs()
+            .filter(|dir| dir.get_file(dir.path().join("vem.toml")).is_some())
+            .filter_map(|dir| dir.path().file_name().and_then(|n| n.to_str()).map(str::to_string))
+            .collect();
+
+        if let Ok(entries) = fs::read_dir(&self.user_root) {
+            for entry in entries {
+                let path = entry?.path();
+                if path.join("vem.toml").is_file()
+                    && let Some(name) = path.file_name().and_then(|n| n.to_str())
+                    && !names.iter().any(|n| n == name)
+                {
+                    names.push(name.to_string());
+                }
+            }
+        }
+
+        names.sort();
+        names.iter().map(|name| self.get(name)).collect()
+    }
+
+    /// Get a template by name
+    fn get(&self, name: &str) -> Result<TEMPLATE, vem_error_t> {
+        if name.is_empty() || name.contains('/') || name.contains('\\') || name == "." || name == ".." {
+            return Err(vem_error_t::TemplateNotFound(name.to_string()));
+        }
+
+        let dir = self.user_root.join(name);
+        let manifest_path = dir.join("vem.toml");
+        if manifest_path.is_file() {
+            let manifest = manifest_rep::new().load(&manifest_path)?;
+            let mut files = Vec::new();
+            collect_files(&dir, Path::new(""), &mut files)?;
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+
+            return Ok(TEMPLATE {
+                name: name.to_string(),
+                origin: dir.display().to_string(),
+                manifest,
+                files,
+            });
+        }
+
+        let Some(dir) = BUNDLED_ENVS.get_dir(name) else {
+            return Err(vem_error_t::TemplateNotFound(name.to_string()));
+        };
+        let Some(source) = dir.get_file(dir.path().join("vem.toml")).and_then(|f| f.contents_utf8()) else {
+            return Err(vem_error_t::TemplateNotFound(name.to_string()));
+        };
+
+        let manifest = manifest_rep::new().parse(source, &format!("<bundled>/{}/vem.toml", name))?;
+        let mut files = Vec::new();
+        collect_bundled_files(dir, dir.path(), &mut files);
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(TEMPLATE {
+            name: name.to_string(),
+            origin: "bundled".to_string(),
+            manifest,
+            files,
+        })
+    }
+
+    /// Write a template's files into `dest`, refusing to overwrite existing files
+    fn extract(&self, name: &str, dest: &Path) -> Result<TEMPLATE, vem_error_t> {
+        let template = self.get(name)?;
+
+        for file in &template.files {
+            let target = dest.join(&file.path);
+            if target.exists() {
+                return Err(vem_error_t::FileSystemError(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("refusing to overwrite {}", target.display()),
+                )));
+            }
+        }
+
+        for file in &template.files {
+            let target = dest.join(&file.path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, &file.contents)?;
+        }
+
+        Ok(template)
+    }
+
+    /// Install the bundled global configs under VEM_HOME/global, keeping user edits
+    fn install_global(&self) -> Result<(), vem_error_t> {
+        let mut files = Vec::new();
+        collect_bundled_files(&BUNDLED_GLOBAL, BUNDLED_GLOBAL.path(), &mut files);
+
+        for file in files {
+            let target = self.global_root.join(&file.path);
+            if target.exists() {
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, &file.contents)?;
+        }
+        Ok(())
+    }
+}
+
+// Read every regular file below `dir` into memory
+fn collect_files(dir: &Path, relative: &Path, files: &mut Vec<TEMPLATE_FILE>) -> Result<(), vem_error_t> {
+    for entry in fs::read_dir(dir.join(relative))? {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(dir, &path, files)?;
+        } else if file_type.is_file() {
+            files.push(TEMPLATE_FILE {
+                contents: fs::read(entry.path())?,
+                path,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Copy every embedded file below `dir`, with paths relative to `root`
+fn collect_bundled_files(dir: &Dir<'_>, root: &Path, files: &mut Vec<TEMPLATE_FILE>) {
+    for file in dir.files() {
+        files.push(TEMPLATE_FILE {
+            path: file.path().strip_prefix(root).unwrap_or(file.path()).to_path_buf(),
+            contents: file.contents().to_vec(),
+        });
+    }
+    for sub in dir.dirs() {
+        collect_bundled_files(sub, root, files);
+    }
+}
+
+/// Factory function to create template repository
+pub fn new() -> impl TemplateRepository {
+    template_repository::new(template_root(), get_vem_home().join("global"))
+}
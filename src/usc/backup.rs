@@ -0,0 +1,120 @@
+use crate::cnf::application::app_config;
+use crate::ent::response::backup::BACKUP as ResponseBackup;
+use crate::ent::response::backup::BACKUPS as ResponseBackups;
+use crate::rep::backup::{self as backup_rep, BackupRepository};
+use crate::rep::environment::{self as environment_rep, EnvironmentRepository};
+use crate::usc::environment::{self as environment_usc, EnvironmentUsecase};
+use crate::util::error::vem_error_t;
+use crate::util::mcode;
+
+pub trait BackupUsecase {
+    fn create(&self, name: &str) -> Result<ResponseBackup, vem_error_t>;
+    fn list(&self, name: Option<&str>) -> Result<ResponseBackups, vem_error_t>;
+    fn restore(&self, name: &str, id: Option<&str>) -> Result<ResponseBackup, vem_error_t>;
+    fn prune(&self, name: Option<&str>, retention_days: Option<u32>) -> Result<ResponseBackups, vem_error_t>;
+}
+
+/// Backup use case backed by backup and environment repositories, switching through the environment use case
+pub struct backup_usecase {
+    repository: Box<dyn BackupRepository>,
+    environments: Box<dyn EnvironmentRepository>,
+    activation: Box<dyn EnvironmentUsecase>,
+    retention_days: u32,
+}
+
+impl backup_usecase {
+    pub fn new(
+        repository: Box<dyn BackupRepository>,
+        environments: Box<dyn EnvironmentRepository>,
+        activation: Box<dyn EnvironmentUsecase>,
+        retention_days: u32,
+    ) -> Self {
+        Self { repository, environments, activation, retention_days }
+    }
+}
+
+impl BackupUsecase for backup_usecase {
+    /// Back up an environment now
+    fn create(&self, name: &str) -> Result<ResponseBackup, vem_error_t> {
+        let backup = self.repository.create(name)?;
+
+        Ok(ResponseBackup {
+            mcode: "VBC1".to_string(),
+            messages: format!("Backup '{}' of environment '{}' created", backup.id, name),
+            backup,
+        })
+    }
+
+    /// List backups for one environment or all of them
+    fn list(&self, name: Option<&str>) -> Result<ResponseBackups, vem_error_t> {
+        let backups = self.repository.list(name)?;
+        if backups.is_empty() {
+            return Ok(ResponseBackups {
+                mcode: "VBL3".to_string(),
+                messages: mcode::VBL3.to_string(),
+                backups,
+            });
+        }
+
+        Ok(ResponseBackups {
+            mcode: "VBL1".to_string(),
+            messages: format!("{} backup(s) found", backups.len()),
+            backups,
+        })
+    }
+
+    /// Restore an environment from a backup (latest when no id is given)
+    fn restore(&self, name: &str, id: Option<&str>) -> Result<ResponseBackup, vem_error_t> {
+        let backup = self.repository.get(name, id)?;
+
+        // Only the global `current` mirrors the environment; a shell's VEM_ENV does not. Clearing it
+        // first saves anything written into a hard-link mirror, so the snapshot below keeps it too.
+        let linked = matches!(self.environments.get_linked(), Ok(current) if current.name == name);
+        if linked {
+            self.environments.unset_current()?;
+        }
+
+        // Keep what is being replaced, unless the environment no longer exists
+        let restored = match self.environments.get(name) {
+            Ok(_) => self.repository.snapshot(name).map(|_| ()),
+            Err(vem_error_t::EnvironmentNotFound(_)) => Ok(()),
+            Err(err) => Err(err),
+        }
+        .and_then(|()| self.repository.restore(&backup).map(|_| ()));
+
+        // Switch back the way `vem switch env` does, relinking dotfiles, even when the restore failed
+        if linked {
+            self.activation.set_current(name)?;
+        }
+        restored?;
+
+        Ok(ResponseBackup {
+            mcode: "VBR1".to_string(),
+            messages: format!("Environment '{}' restored from backup '{}'", name, backup.id),
+            backup,
+        })
+    }
+
+    /// Delete backups older than the retention window (backup_retention_days by default)
+    fn prune(&self, name: Option<&str>, retention_days: Option<u32>) -> Result<ResponseBackups, vem_error_t> {
+        let retention_days = retention_days.unwrap_or(self.retention_days);
+        let backups = self.repository.prune(name, retention_days)?;
+
+        Ok(ResponseBackups {
+            mcode: "VBP1".to_string(),
+            messages: format!("{} backup(s) older than {} day(s) pruned", backups.len(), retention_days),
+            backups,
+        })
+    }
+}
+
+/// Factory function to create backup use case
+pub fn new(config: app_config) -> impl BackupUsecase {
+    let retention_days = config.backup_retention_days;
+    backup_usecase::new(
+        Box::new(backup_rep::new(config.clone())),
+        Box::new(environment_rep::new(config.clone())),
+        Box::new(environment_usc::new(config)),
+        retention_days,
+    )
+}
@@ -0,0 +1,696 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::cnf::application::{app_config, expand_home};
+use crate::ent::model::ctags::{CTAG, ctag_status_t};
+use crate::ent::model::manifest::{CTAGS, CTAGS_REPOSITORY, CTAGS_TAG_SET, MANIFEST};
+use crate::ent::request::ctags::CTAGS_GENERATE as RequestCtagsGenerate;
+use crate::ent::response::ctags::CTAGS as ResponseCtags;
+use crate::rep::backup::{self as backup_rep, BackupRepository};
+use crate::rep::ctags::{self as ctags_rep, CtagsRepository, ctags_command_t, excluded, resolve_path, tag_file_name};
+use crate::usc::environment::{self as environment_usc, EnvironmentUsecase};
+use crate::util::error::vem_error_t;
+use crate::util::mcode;
+
+// Columns `list ctags` can fill, in the default order used when list_fields is empty
+const LIST_FIELDS: [&str; 6] = ["name", "repository", "tag_file", "last_updated", "size", "status"];
+const DEFAULT_LIST_FIELDS: usize = 5;
+
+// ctags options used when vem.toml gives none for the command
+const DEFAULT_OPTIONS: [&str; 2] = ["--recurse=yes", "--sort=yes"];
+
+// Common language names that differ from the ctags parser names
+const LANGUAGE_ALIASES: [(&str, &str); 13] = [
+    ("bash", "Sh"),
+    ("shell", "Sh"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("csharp", "C#"),
+    ("cs", "C#"),
+    ("js", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("py", "Python"),
+    ("golang", "Go"),
+    ("yml", "Yaml"),
+    ("vimscript", "Vim"),
+    ("viml", "Vim"),
+];
+
+pub trait CtagsUsecase {
+    fn generate(&self, request: RequestCtagsGenerate) -> Result<ResponseCtags, vem_error_t>;
+    fn update(&self, request: RequestCtagsGenerate) -> Result<ResponseCtags, vem_error_t>;
+    fn delete(
+        &self,
+        environment: Option<&str>,
+        repository: &str,
+        tag_file: Option<&str>,
+    ) -> Result<ResponseCtags, vem_error_t>;
+    fn list(&self, environment: Option<&str>, repository: Option<&str>) -> Result<ResponseCtags, vem_error_t>;
+    fn clean(&self, environment: Option<&str>, backup: bool) -> Result<ResponseCtags, vem_error_t>;
+    fn clean_confirm(&self, environment: Option<&str>) -> Result<bool, vem_error_t>;
+    fn watch(
+        &self,
+        request: RequestCtagsGenerate,
+        debounce: Duration,
+        report: &mut dyn FnMut(Result<ResponseCtags, vem_error_t>),
+    ) -> Result<(), vem_error_t>;
+}
+
+// Outcome of running ctags over the selected repositories
+struct ctags_run_t {
+    environment: String,
+    tags: Vec<CTAG>,
+    skipped: Vec<String>, // Repositories whose path does not exist
+    rebuilt: Vec<String>, // Repositories updated without a file manifest to compare against
+    retagged: usize,
+    removed: usize,
+    fields: Vec<String>,
+    warnings: Vec<String>, // From loading vem.toml
+}
+
+/// Ctags use case backed by the ctags repository and environment resolution
+pub struct ctags_usecase {
+    repository: Box<dyn CtagsRepository>,
+    backups: Box<dyn BackupRepository>,
+    environments: Box<dyn EnvironmentUsecase>,
+}
+
+impl ctags_usecase {
+    pub fn new(
+        repository: Box<dyn CtagsRepository>,
+        backups: Box<dyn BackupRepository>,
+        environments: Box<dyn EnvironmentUsecase>,
+    ) -> Self {
+        Self { repository, backups, environments }
+    }
+
+    // The named environment, or the current one
+    fn environment(&self, name: Option<&str>) -> Result<String, vem_error_t> {
+        let response = match name {
+            Some(name) => self.environments.get(name)?,
+            None => self.environments.get_current()?,
+        };
+        Ok(response.environment.name)
+    }
+
+    // Run ctags for one repository or every enabled one. Updates re-tag only changed files unless
+    // forced; those changes are spliced into a file built with generate_options, so only a forced
+    // update, which rebuilds the whole file, uses update_options.
+    fn run(&self, request: &RequestCtagsGenerate, update: bool) -> Result<ctags_run_t, vem_error_t> {
+        let environment = self.environment(request.environment.as_deref())?;
+        let manifest = self.repository.manifest(&environment)?;
+        let ctags = enabled(&manifest, &environment)?;
+
+        let selected = match &request.repository {
+            Some(name) => vec![find_repository(ctags, name)?],
+            None => ctags.repositories.iter().filter(|(_, repository)| repository.enabled).collect(),
+        };
+        let options = if update && request.force {
+            &ctags.commands.update_options
+        } else {
+            &ctags.commands.generate_options
+        };
+        let languages: Vec<String> = request.languages.iter().map(|language| language_alias(language).to_string()).collect();
+        let command = command(ctags, options, &[], &request.exclude, &languages);
+
+        // Relative repository paths are relative to the project vem is run from
+        let base = std::env::current_dir()?;
+        let mut run = ctags_run_t {
+            environment: environment.clone(),
+            tags: Vec::new(),
+            skipped: Vec::new(),
+            rebuilt: Vec::new(),
+            retagged: 0,
+            removed: 0,
+            fields: list_fields(ctags),
+            warnings: manifest.warnings.clone(),
+        };
+        for (key, repository) in selected {
+            // A named repository must exist; with --all, checkouts missing on this machine are skipped
+            let source = match source_path(key, repository, &base) {
+                Ok(source) => source,
+                Err(_) if request.repository.is_none() => {
+                    run.skipped.push(key.clone());
+                    continue;
+                },
+                Err(err) => return Err(err),
+            };
+            let mut tag = if update && !request.force {
+                let delta = self.repository.update(&environment, key, &[source], &command)?;
+                if delta.rebuilt {
+                    run.rebuilt.push(key.clone());
+                }
+                run.retagged += delta.retagged;
+                run.removed += delta.removed;
+                delta.tag
+            } else {
+                self.repository.generate(&environment, key, &[source], &command)?
+            };
+            annotate(&mut tag, ctags);
+            run.tags.push(tag);
+        }
+        Ok(run)
+    }
+
+    // Tag the sources of a [ctags.tags.*] set, across its repositories, into the set's one tag file
+    fn generate_set(&self, request: &RequestCtagsGenerate, name: &str) -> Result<ResponseCtags, vem_error_t> {
+        let environment = self.environment(request.environment.as_deref())?;
+        let manifest = self.repository.manifest(&environment)?;
+        let ctags = enabled(&manifest, &environment)?;
+        let (key, set) = find_tag_set(ctags, name)?;
+
+        // A set without repositories covers the project vem is run from; missing checkouts are skipped
+        let base = std::env::current_dir()?;
+        let mut roots = Vec::new();
+        let mut skipped = Vec::new();
+        if set.repositories.is_empty() {
+            roots.push(base.clone());
+        }
+        for repository in &set.repositories {
+            let (repository_key, repository) = find_repository(ctags, repository)?;
+            match source_path(repository_key, repository, &base) {
+                Ok(root) => roots.push(root),
+                Err(_) => skipped.push(repository_key.clone()),
+            }
+        }
+
+        // source_dirs are relative to each repository; a repository need not have all of them
+        let source_dirs = if set.source_dirs.is_empty() { vec![".".to_string()] } else { set.source_dirs.clone() };
+        let mut sources = Vec::new();
+        for root in &roots {
+            for dir in &source_dirs {
+                let path = resolve_path(dir, root);
+                if path.is_dir() {
+                    sources.push(path.canonicalize()?);
+                }
+            }
+        }
+        if sources.is_empty() {
+            return Err(vem_error_t::ConfigurationError(format!(
+                "none of the source_dirs of tag set '{}' exist in its repositories",
+                key
+            )));
+        }
+
+        let requested = if request.languages.is_empty() { &set.languages } else { &request.languages };
+        let (languages, unsupported) = self.languages(ctags, requested);
+        if languages.is_empty() && !requested.is_empty() {
+            return Err(vem_error_t::ConfigurationError(format!(
+                "ctags supports none of the languages of tag set '{}': {}",
+                key,
+                requested.join(", ")
+            )));
+        }
+        let exclude: Vec<String> = set.exclude_patterns.iter().chain(&request.exclude).cloned().collect();
+        let command = command(ctags, &ctags.commands.generate_options, &set.custom_options, &exclude, &languages);
+
+        let mut tag = self.repository.generate_set(
+            &environment,
+            tag_set_file(set),
+            &sources,
+            &set.include_patterns,
+            &command,
+        )?;
+        annotate(&mut tag, ctags);
+        self.wire(&environment)?;
+
+        let mut messages = format!(
+            "Generated {} for tag set '{}' in environment '{}'",
+            tag.tag_file.display(),
+            key,
+            environment
+        );
+        if !skipped.is_empty() {
+            messages.push_str(&format!("; skipped {} (path not found)", skipped.join(", ")));
+        }
+        if !unsupported.is_empty() {
+            messages.push_str(&format!("; languages not supported by ctags: {}", unsupported.join(", ")));
+        }
+        Ok(ResponseCtags {
+            mcode: "VCTG1".to_string(),
+            messages,
+            environment,
+            tags: vec![tag],
+            fields: list_fields(ctags),
+            warnings: manifest.warnings.clone(),
+        })
+    }
+
+    // Point the editor's 'tags' at the tag files present: enabled repositories by priority, then tag sets.
+    // Environments without a readable vem.toml are left alone.
+    fn wire(&self, environment: &str) -> Result<(), vem_error_t> {
+        let Ok(manifest) = self.repository.manifest(environment) else {
+            return Ok(());
+        };
+        let ctags = &manifest.ctags;
+        let present = self.repository.list(environment)?;
+        let tag_file = |name: &str| present.iter().find(|tag| tag.name == name).map(|tag| tag.tag_file.clone());
+
+        let mut tag_files = Vec::new();
+        if ctags.enabled {
+            let mut repositories: Vec<_> = ctags.repositories.iter().filter(|(_, repository)| repository.enabled).collect();
+            repositories.sort_by_key(|(_, repository)| repository.priority);
+            tag_files.extend(repositories.iter().filter_map(|(key, _)| tag_file(key)));
+            for set in ctags.tags.values() {
+                if let Some(path) = tag_file(tag_set_file(set))
+                    && !tag_files.contains(&path)
+                {
+                    tag_files.push(path);
+                }
+            }
+        }
+        self.repository.write_tags_option(environment, &manifest.editor.editor_type, &tag_files)?;
+        Ok(())
+    }
+
+    // Map language names onto the parsers ctags lists, returning those it lacks separately.
+    // When ctags cannot be asked, the names are passed through.
+    fn languages(&self, ctags: &CTAGS, requested: &[String]) -> (Vec<String>, Vec<String>) {
+        let known = self.repository.languages(&expand_home(&ctags.executable)).ok();
+        let mut languages: Vec<String> = Vec::new();
+        let mut unsupported = Vec::new();
+        for language in requested {
+            let alias = language_alias(language);
+            let found = match &known {
+                Some(known) => known.iter().find(|name| name.eq_ignore_ascii_case(alias)).cloned(),
+                None => Some(alias.to_string()),
+            };
+            match found {
+                Some(found) if !languages.contains(&found) => languages.push(found),
+                Some(_) => {},
+                None => unsupported.push(language.clone()),
+            }
+        }
+        (languages, unsupported)
+    }
+}
+
+impl CtagsUsecase for ctags_usecase {
+    /// Generate tags for a repository defined in vem.toml
+    fn generate(&self, request: RequestCtagsGenerate) -> Result<ResponseCtags, vem_error_t> {
+        if let Some(name) = &request.tag_set {
+            return self.generate_set(&request, name);
+        }
+        let run = self.run(&request, false)?;
+        self.wire(&run.environment)?;
+        let messages = match run.tags.as_slice() {
+            [tag] => format!(
+                "Generated {} for repository '{}' in environment '{}'",
+                tag.tag_file.display(),
+                tag.name,
+                run.environment
+            ),
+            tags => format!("Generated tags for {} repository(ies) in environment '{}'", tags.len(), run.environment),
+        };
+
+        Ok(ResponseCtags {
+            mcode: "VCTG1".to_string(),
+            messages,
+            environment: run.environment,
+            tags: run.tags,
+            fields: run.fields,
+            warnings: run.warnings,
+        })
+    }
+
+    /// Bring tags for one repository or every enabled one up to date
+    fn update(&self, request: RequestCtagsGenerate) -> Result<ResponseCtags, vem_error_t> {
+        let run = self.run(&request, true)?;
+        self.wire(&run.environment)?;
+        Ok(updated(run, request.force))
+    }
+
+    /// Delete a repository's or tag set's tag file, or a tag file by name
+    fn delete(
+        &self,
+        environment: Option<&str>,
+        repository: &str,
+        tag_file: Option<&str>,
+    ) -> Result<ResponseCtags, vem_error_t> {
+        let environment = self.environment(environment)?;
+        // Tags of a repository since removed from vem.toml can still be deleted by key
+        let manifest = self.repository.manifest(&environment).ok();
+        let key = manifest
+            .as_ref()
+            .and_then(|manifest| {
+                let ctags = &manifest.ctags;
+                find_repository(ctags, repository)
+                    .map(|(key, _)| key.clone())
+                    .or_else(|_| find_tag_set(ctags, repository).map(|(_, set)| tag_set_file(set).to_string()))
+                    .ok()
+            })
+            .unwrap_or_else(|| repository.to_string());
+        let file_name = match tag_file {
+            Some(tag_file) => tag_file.to_string(),
+            None => tag_file_name(&key),
+        };
+
+        let path = self.repository.delete(&environment, &file_name)?;
+        self.wire(&environment)?;
+        Ok(ResponseCtags {
+            mcode: "VCTD1".to_string(),
+            messages: format!("Deleted {} from environment '{}'", path.display(), environment),
+            environment,
+            tags: Vec::new(),
+            fields: manifest.as_ref().map(|manifest| list_fields(&manifest.ctags)).unwrap_or_else(default_fields),
+            warnings: manifest.map(|manifest| manifest.warnings).unwrap_or_default(),
+        })
+    }
+
+    /// List the environment's tag files, optionally for one repository
+    fn list(&self, environment: Option<&str>, repository: Option<&str>) -> Result<ResponseCtags, vem_error_t> {
+        let environment = self.environment(environment)?;
+        let manifest = self.repository.manifest(&environment).ok();
+        let mut tags = self.repository.list(&environment)?;
+        if let Some(manifest) = &manifest {
+            for tag in &mut tags {
+                annotate(tag, &manifest.ctags);
+            }
+        }
+        if let Some(repository) = repository {
+            tags.retain(|tag| tag.name == repository || tag.repository == repository);
+        }
+        let fields = manifest.as_ref().map(|manifest| list_fields(&manifest.ctags)).unwrap_or_else(default_fields);
+
+        let (mcode, messages) = if tags.is_empty() {
+            ("VCTL3", mcode::VCTL3.to_string())
+        } else {
+            ("VCTL1", format!("{} tag file(s) in environment '{}'", tags.len(), environment))
+        };
+        Ok(ResponseCtags {
+            mcode: mcode.to_string(),
+            messages,
+            environment,
+            tags,
+            fields,
+            warnings: manifest.map(|manifest| manifest.warnings).unwrap_or_default(),
+        })
+    }
+
+    /// Whether `clean ctags` should ask first, from clean_confirm
+    fn clean_confirm(&self, environment: Option<&str>) -> Result<bool, vem_error_t> {
+        let environment = self.environment(environment)?;
+        let manifest = self.repository.manifest(&environment).ok();
+        Ok(manifest.is_none_or(|manifest| manifest.ctags.commands.clean_confirm))
+    }
+
+    /// Remove every tag file of the environment, backing the environment up first when clean_backup is set
+    fn clean(&self, environment: Option<&str>, backup: bool) -> Result<ResponseCtags, vem_error_t> {
+        let environment = self.environment(environment)?;
+        let manifest = self.repository.manifest(&environment).ok();
+        let clean_backup = manifest.as_ref().is_none_or(|manifest| manifest.ctags.commands.clean_backup);
+
+        let snapshot = if backup && clean_backup { self.backups.snapshot(&environment)? } else { None };
+        let removed = self.repository.clean(&environment)?;
+        self.wire(&environment)?;
+
+        let mut messages = format!("Removed {} tag file(s) from environment '{}'", removed.len(), environment);
+        if let Some(snapshot) = snapshot {
+            messages.push_str(&format!(" (backup '{}')", snapshot.id));
+        }
+        Ok(ResponseCtags {
+            mcode: "VCTC1".to_string(),
+            messages,
+            environment,
+            tags: Vec::new(),
+            fields: manifest.as_ref().map(|manifest| list_fields(&manifest.ctags)).unwrap_or_else(default_fields),
+            warnings: manifest.map(|manifest| manifest.warnings).unwrap_or_default(),
+        })
+    }
+
+    /// Watch the sources of one repository or every enabled one, and after each burst of changes
+    /// re-tag the changed files of the repositories they belong to. Runs until the watch fails;
+    /// every update that changed something, and every failed one, goes to `report`.
+    fn watch(
+        &self,
+        request: RequestCtagsGenerate,
+        debounce: Duration,
+        report: &mut dyn FnMut(Result<ResponseCtags, vem_error_t>),
+    ) -> Result<(), vem_error_t> {
+        let environment = self.environment(request.environment.as_deref())?;
+        let manifest = self.repository.manifest(&environment)?;
+        let ctags = enabled(&manifest, &environment)?;
+        if !ctags.update_on_save {
+            return Err(vem_error_t::ConfigurationError(format!(
+                "update_on_save is off for environment '{}'; set [ctags] update_on_save = true in its vem.toml to watch",
+                environment
+            )));
+        }
+
+        let selected = match &request.repository {
+            Some(name) => vec![find_repository(ctags, name)?],
+            None => ctags.repositories.iter().filter(|(_, repository)| repository.enabled).collect(),
+        };
+        let base = std::env::current_dir()?;
+        let mut sources = Vec::new();
+        let mut skipped = Vec::new();
+        for (key, repository) in selected {
+            match source_path(key, repository, &base) {
+                Ok(source) => sources.push((key.clone(), source)),
+                Err(_) if request.repository.is_none() => skipped.push(key.clone()),
+                Err(err) => return Err(err),
+            }
+        }
+        if sources.is_empty() {
+            return Err(vem_error_t::ConfigurationError(format!(
+                "no enabled repository of environment '{}' has a path to watch",
+                environment
+            )));
+        }
+        // Watching re-tags incrementally, so it excludes what the incremental update does
+        let exclude = command(ctags, &ctags.commands.generate_options, &[], &request.exclude, &[]).exclude;
+        let paths: Vec<PathBuf> = sources.iter().map(|(_, source)| source.clone()).collect();
+        let mut watch = self.repository.watch(&paths, &exclude)?;
+
+        let keys: Vec<&str> = sources.iter().map(|(key, _)| key.as_str()).collect();
+        let mut messages = format!("Watching {} in environment '{}'", keys.join(", "), environment);
+        if !skipped.is_empty() {
+            messages.push_str(&format!("; skipped {} (path not found)", skipped.join(", ")));
+        }
+        report(Ok(ResponseCtags {
+            mcode: "VCTW1".to_string(),
+            messages,
+            environment: environment.clone(),
+            tags: Vec::new(),
+            fields: list_fields(ctags),
+            warnings: manifest.warnings.clone(),
+        }));
+
+        let update = |key: Option<&str>| RequestCtagsGenerate {
+            environment: Some(environment.clone()),
+            repository: key.map(String::from).or_else(|| request.repository.clone()),
+            tag_set: None,
+            languages: request.languages.clone(),
+            exclude: request.exclude.clone(),
+            force: false,
+        };
+        let mut regenerate = |request: RequestCtagsGenerate| {
+            let result = self.run(&request, true).and_then(|run| {
+                self.wire(&run.environment)?;
+                Ok(run)
+            });
+            match result {
+                Ok(run) if run.retagged + run.removed > 0 || !run.rebuilt.is_empty() => {
+                    // Warnings were reported when watching started
+                    let mut response = updated(run, false);
+                    response.mcode = "VCTW3".to_string();
+                    response.warnings.clear();
+                    report(Ok(response));
+                },
+                Ok(_) => {},
+                Err(err) => report(Err(err)),
+            }
+        };
+
+        // Catch up on changes made while nothing was watching
+        if ctags.auto_generate {
+            regenerate(update(None));
+        }
+
+        loop {
+            // Wait for a change, then for `debounce` without one, so a burst of saves is one update
+            let mut changed = BTreeSet::new();
+            let mut timeout = None;
+            while let Some(paths) = watch.next(timeout)? {
+                // A path can belong to several repositories when one is checked out inside another
+                for path in paths {
+                    for (key, source) in &sources {
+                        if path.starts_with(source) && !excluded(&path, source, &exclude) {
+                            changed.insert(key.as_str());
+                        }
+                    }
+                }
+                if !changed.is_empty() {
+                    timeout = Some(debounce);
+                }
+            }
+            for key in changed {
+                regenerate(update(Some(key)));
+            }
+        }
+    }
+}
+
+// Response for an update run, with what it re-tagged unless it was a forced full rebuild
+fn updated(run: ctags_run_t, force: bool) -> ResponseCtags {
+    let (mcode, mut messages) = match run.tags.as_slice() {
+        [] => ("VCTU3", mcode::VCTU3.to_string()),
+        [tag] => ("VCTU1", format!("Updated tags for repository '{}' in environment '{}'", tag.name, run.environment)),
+        tags => (
+            "VCTU1",
+            format!("Updated tags for {} repository(ies) in environment '{}'", tags.len(), run.environment),
+        ),
+    };
+    if !run.tags.is_empty() && !force {
+        messages.push_str(&format!(" ({} file(s) re-tagged, {} removed", run.retagged, run.removed));
+        if !run.rebuilt.is_empty() {
+            messages.push_str(&format!("; full rebuild of {}", run.rebuilt.join(", ")));
+        }
+        messages.push(')');
+    }
+    if !run.skipped.is_empty() {
+        messages.push_str(&format!("; skipped {} (path not found)", run.skipped.join(", ")));
+    }
+
+    ResponseCtags {
+        mcode: mcode.to_string(),
+        messages,
+        environment: run.environment,
+        tags: run.tags,
+        fields: run.fields,
+        warnings: run.warnings,
+    }
+}
+
+// [ctags] of an environment that has ctags turned on
+fn enabled<'a>(manifest: &'a MANIFEST, environment: &str) -> Result<&'a CTAGS, vem_error_t> {
+    if !manifest.ctags.enabled {
+        return Err(vem_error_t::ConfigurationError(format!(
+            "ctags are disabled for environment '{}'; set [ctags] enabled = true in its vem.toml",
+            environment
+        )));
+    }
+    Ok(&manifest.ctags)
+}
+
+// Look a repository up by its key, then by its name field
+fn find_repository<'a>(ctags: &'a CTAGS, name: &str) -> Result<(&'a String, &'a CTAGS_REPOSITORY), vem_error_t> {
+    ctags
+        .repositories
+        .get_key_value(name)
+        .or_else(|| ctags.repositories.iter().find(|(_, repository)| repository.name == name))
+        .ok_or_else(|| vem_error_t::CtagsRepositoryNotFound(name.to_string()))
+}
+
+// Look a tag set up by its key, then by its name field
+fn find_tag_set<'a>(ctags: &'a CTAGS, name: &str) -> Result<(&'a String, &'a CTAGS_TAG_SET), vem_error_t> {
+    ctags
+        .tags
+        .get_key_value(name)
+        .or_else(|| ctags.tags.iter().find(|(_, set)| set.name == name))
+        .ok_or_else(|| vem_error_t::TagSetNotFound(name.to_string()))
+}
+
+// Name a tag set's file is stored under: its tag_file, without a .tags extension
+fn tag_set_file(set: &CTAGS_TAG_SET) -> &str {
+    set.tag_file.strip_suffix(".tags").unwrap_or(&set.tag_file)
+}
+
+// ctags parser name for a language
+fn language_alias(language: &str) -> &str {
+    LANGUAGE_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(language))
+        .map_or(language, |(_, name)| name)
+}
+
+// Absolute path of a repository's sources
+fn source_path(key: &str, repository: &CTAGS_REPOSITORY, base: &Path) -> Result<PathBuf, vem_error_t> {
+    let path = resolve_path(&repository.path, base);
+    if !path.is_dir() {
+        return Err(vem_error_t::ConfigurationError(format!(
+            "path '{}' of repository '{}' is not a directory",
+            path.display(),
+            key
+        )));
+    }
+    Ok(path.canonicalize()?)
+}
+
+// ctags program and options: global config file, command options, [ctags.global], tag set options,
+// then the exclude patterns and languages
+fn command(
+    ctags: &CTAGS,
+    options: &[String],
+    custom_options: &[String],
+    exclude: &[String],
+    languages: &[String],
+) -> ctags_command_t {
+    let mut args = Vec::new();
+    if let Some(config_file) = &ctags.global_config_file {
+        let config_file = expand_home(config_file);
+        if config_file.is_file() {
+            args.push(format!("--options={}", config_file.display()));
+        }
+    }
+    if options.is_empty() {
+        args.extend(DEFAULT_OPTIONS.map(String::from));
+    } else {
+        args.extend(options.iter().cloned());
+    }
+    args.extend(ctags.global.custom_options.iter().cloned());
+    args.extend(custom_options.iter().cloned());
+    for pattern in ctags.global.exclude_patterns.iter().chain(exclude) {
+        args.push(format!("--exclude={}", pattern));
+    }
+    if !languages.is_empty() {
+        args.push(format!("--languages={}", languages.join(",")));
+    }
+
+    // Every --exclude, including those written into the configured options, also limits the file manifest
+    let exclude = args.iter().filter_map(|arg| arg.strip_prefix("--exclude=")).map(String::from).collect();
+    ctags_command_t {
+        program: expand_home(&ctags.executable),
+        options: args,
+        exclude,
+    }
+}
+
+// Fill in a tag file's repository and status from vem.toml; a tag set's file shows the set's name
+fn annotate(tag: &mut CTAG, ctags: &CTAGS) {
+    if let Some(repository) = ctags.repositories.get(&tag.name) {
+        tag.repository = repository.name.clone();
+        tag.status = if repository.enabled { ctag_status_t::ACTIVE } else { ctag_status_t::DISABLED };
+    } else if let Some(set) = ctags.tags.values().find(|set| tag_set_file(set) == tag.name) {
+        tag.repository = set.name.clone();
+        tag.status = ctag_status_t::ACTIVE;
+    } else {
+        tag.status = ctag_status_t::ORPHANED;
+    }
+}
+
+// The configured list_fields that `list ctags` can fill; fields for other tools are skipped
+fn list_fields(ctags: &CTAGS) -> Vec<String> {
+    let fields: Vec<String> = ctags
+        .commands
+        .list_fields
+        .iter()
+        .filter(|field| LIST_FIELDS.contains(&field.as_str()))
+        .cloned()
+        .collect();
+    if fields.is_empty() { default_fields() } else { fields }
+}
+
+fn default_fields() -> Vec<String> {
+    LIST_FIELDS[..DEFAULT_LIST_FIELDS].iter().map(|field| field.to_string()).collect()
+}
+
+/// Factory function to create ctags use case
+pub fn new(config: app_config) -> impl CtagsUsecase {
+    ctags_usecase::new(
+        Box::new(ctags_rep::new(config.clone())),
+        Box::new(backup_rep::new(config.clone())),
+        Box::new(environment_usc::new(config)),
+    )
+}
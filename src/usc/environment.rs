@@ -1,6 +1,545 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::cnf::application::{app_config, current_link_path, session_environment};
+use crate::ent::model::backup::BACKUP;
+use crate::ent::model::environment::ENVIRONMENT;
+use crate::ent::model::local::LOCAL;
+use crate::ent::model::manifest::editor_type_t;
 use crate::ent::request::environment::ENVIRONMENT as RequestEnvironment;
+use crate::ent::request::environment::ENVIRONMENT_LIST as RequestEnvironmentList;
+use crate::ent::request::environment::ENVIRONMENT_UPDATE as RequestEnvironmentUpdate;
+use crate::ent::request::environment::environment_sort_t;
 use crate::ent::response::environment::ENVIRONMENT as ResponseEnvironment;
+use crate::ent::response::environment::ENVIRONMENTS as ResponseEnvironments;
+use crate::ent::response::environment::ENVIRONMENT_DEFAULT as ResponseEnvironmentDefault;
+use crate::ent::response::environment::ENVIRONMENT_DEACTIVATE as ResponseEnvironmentDeactivate;
+use crate::ent::response::environment::ENVIRONMENT_LAUNCH as ResponseEnvironmentLaunch;
+use crate::ent::response::environment::ENVIRONMENT_LOCAL as ResponseEnvironmentLocal;
+use crate::ent::response::environment::ENVIRONMENT_EXPLAIN as ResponseEnvironmentExplain;
+use crate::ent::response::environment::CURRENT_SOURCE;
+use crate::rep::activation::{self as activation_rep, ActivationRepository};
+use crate::rep::backup::{self as backup_rep, BackupRepository};
+use crate::rep::environment::{self as environment_rep, EnvironmentRepository};
+use crate::rep::local::{self as local_rep, LocalRepository};
+use crate::rep::template::{self as template_rep, TemplateRepository};
+use crate::util::error::vem_error_t;
+use crate::util::mcode;
 
 pub trait EnvironmentUsecase {
-    fn create(&self, request: RequestEnvironment) -> ResponseEnvironment;
-}
\ No newline at end of file
+    fn create(&self, request: RequestEnvironment) -> Result<ResponseEnvironment, vem_error_t>;
+    fn list(&self, request: RequestEnvironmentList) -> Result<ResponseEnvironments, vem_error_t>;
+    fn get(&self, name: &str) -> Result<ResponseEnvironment, vem_error_t>;
+    fn update(&self, request: RequestEnvironmentUpdate) -> Result<ResponseEnvironment, vem_error_t>;
+    fn delete(&self, name: &str, backup: bool) -> Result<ResponseEnvironment, vem_error_t>;
+    fn check_delete(&self, name: &str) -> Result<(), vem_error_t>;
+    fn undelete(&self, name: &str) -> Result<ResponseEnvironment, vem_error_t>;
+    fn get_current(&self) -> Result<ResponseEnvironment, vem_error_t>;
+    fn auto_switch(&self) -> Result<Option<ResponseEnvironment>, vem_error_t>;
+    fn set_current(&self, name: &str) -> Result<ResponseEnvironment, vem_error_t>;
+    fn set_default(&self, name: Option<&str>) -> Result<ResponseEnvironmentDefault, vem_error_t>;
+    fn deactivate(&self) -> Result<ResponseEnvironmentDeactivate, vem_error_t>;
+    fn launch(&self, name: Option<&str>, session: bool, record_use: bool) -> Result<ResponseEnvironmentLaunch, vem_error_t>;
+    fn get_local(&self) -> Result<ResponseEnvironmentLocal, vem_error_t>;
+    fn set_local(&self, name: Option<&str>) -> Result<ResponseEnvironmentLocal, vem_error_t>;
+    fn explain_current(&self) -> Result<ResponseEnvironmentExplain, vem_error_t>;
+}
+
+/// Environment use case backed by an environment repository
+pub struct environment_usecase {
+    repository: Box<dyn EnvironmentRepository>,
+    templates: Box<dyn TemplateRepository>,
+    backups: Box<dyn BackupRepository>,
+    activation: Box<dyn ActivationRepository>,
+    locals: Box<dyn LocalRepository>,
+    config: app_config,
+}
+
+// Where the current environment was resolved from
+enum current_source_t {
+    SESSION,
+    LOCAL(PathBuf),
+    LINK,
+    DEFAULT,
+}
+
+impl environment_usecase {
+    pub fn new(
+        repository: Box<dyn EnvironmentRepository>,
+        templates: Box<dyn TemplateRepository>,
+        backups: Box<dyn BackupRepository>,
+        activation: Box<dyn ActivationRepository>,
+        locals: Box<dyn LocalRepository>,
+        config: app_config,
+    ) -> Self {
+        Self { repository, templates, backups, activation, locals, config }
+    }
+
+    /// Point `current` at the environment and link the editor's dotfiles into it
+    fn activate(&self, name: &str) -> Result<ENVIRONMENT, vem_error_t> {
+        let environment = self.repository.set_current(name)?;
+        self.activation.activate(name)?;
+        Ok(environment)
+    }
+
+    /// Resolve the current environment: VEM_ENV, then the nearest .vem-env, then `current`, falling back to
+    /// default_environment when that is missing or dangling. Only reports; see `auto_switch` for switching.
+    fn resolve_current(&self) -> Result<(ENVIRONMENT, current_source_t), vem_error_t> {
+        self.resolve(session_environment())
+    }
+
+    /// `resolve_current` with the VEM_ENV value to use, None to skip it
+    fn resolve(&self, session: Option<String>) -> Result<(ENVIRONMENT, current_source_t), vem_error_t> {
+        if let Some(session) = session {
+            return Ok((self.repository.get(&session)?, current_source_t::SESSION));
+        }
+        if let Some(local) = self.find_local()? {
+            return Ok((self.repository.get(&local.environment)?, current_source_t::LOCAL(local.path)));
+        }
+        match self.repository.get_linked() {
+            Ok(environment) => Ok((environment, current_source_t::LINK)),
+            Err(vem_error_t::NoCurrentEnvironment) | Err(vem_error_t::EnvironmentNotFound(_)) => {
+                let Some(default) = self.config.default_environment.as_deref() else {
+                    return Err(vem_error_t::NoCurrentEnvironment);
+                };
+                Ok((self.repository.get(default)?, current_source_t::DEFAULT))
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Nearest .vem-env from the working directory; none when the directory is gone
+    fn find_local(&self) -> Result<Option<LOCAL>, vem_error_t> {
+        match std::env::current_dir() {
+            Ok(cwd) => self.locals.find(&cwd),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl EnvironmentUsecase for environment_usecase {
+    /// Create a new environment
+    fn create(&self, request: RequestEnvironment) -> Result<ResponseEnvironment, vem_error_t> {
+        let template = match &request.template {
+            Some(name) => {
+                let template = self.templates.get(name)?;
+                self.templates.install_global()?;
+                Some(template)
+            },
+            None => None,
+        };
+        let editor_type = match (&template, &request.editor) {
+            (Some(template), _) => &template.manifest.editor.editor_type,
+            (None, Some(editor_type)) => editor_type,
+            (None, None) => &editor_type_t::VIM,
+        };
+        let environment =
+            self.repository.create(&request.name, request.description, request.tags, editor_type, template.as_ref())?;
+
+        let messages = match &template {
+            Some(template) => format!("Environment '{}' created from template '{}'", environment.name, template.name),
+            None => format!("Environment '{}' created", environment.name),
+        };
+        Ok(ResponseEnvironment {
+            mcode: "VEC1".to_string(),
+            messages,
+            environment,
+            warnings: template.map(|template| template.manifest.warnings).unwrap_or_default(),
+        })
+    }
+
+    /// List environments carrying every requested tag, in the requested order
+    fn list(&self, request: RequestEnvironmentList) -> Result<ResponseEnvironments, vem_error_t> {
+        let (mut environments, mut warnings) = self.repository.list()?;
+        environments.retain(|env| request.tags.iter().all(|tag| env.tags.contains(tag)));
+
+        // Timestamps sort most recent first; never-used environments go last
+        match request.sort {
+            environment_sort_t::NAME => environments.sort_by(|a, b| a.name.cmp(&b.name)),
+            environment_sort_t::CREATED => environments.sort_by_key(|env| std::cmp::Reverse(env.created)),
+            environment_sort_t::UPDATED => environments.sort_by_key(|env| std::cmp::Reverse(env.update)),
+            environment_sort_t::LAST_USED => environments.sort_by_key(|env| std::cmp::Reverse(env.last_used)),
+        }
+        if request.reverse {
+            environments.reverse();
+        }
+
+        let current = match self.resolve_current() {
+            Ok((env, _)) => Some(env.name),
+            Err(vem_error_t::NoCurrentEnvironment) | Err(vem_error_t::EnvironmentNotFound(_)) => None,
+            Err(err) => {
+                warnings.push(format!("cannot tell the current environment: {}", err));
+                None
+            },
+        };
+        if request.current_first
+            && let Some(current) = &current
+            && let Some(index) = environments.iter().position(|env| &env.name == current)
+        {
+            let env = environments.remove(index);
+            environments.insert(0, env);
+        }
+
+        let mut sizes = BTreeMap::new();
+        if request.with_size {
+            for env in &environments {
+                sizes.insert(env.name.clone(), self.repository.disk_usage(&env.name)?);
+            }
+        }
+
+        if environments.is_empty() {
+            return Ok(ResponseEnvironments {
+                mcode: "VEL3".to_string(),
+                messages: mcode::VEL3.to_string(),
+                environments,
+                current,
+                sizes,
+                warnings,
+            });
+        }
+
+        Ok(ResponseEnvironments {
+            mcode: "VEL1".to_string(),
+            messages: format!("{} environment(s) found", environments.len()),
+            environments,
+            current,
+            sizes,
+            warnings,
+        })
+    }
+
+    /// Get an environment by name
+    fn get(&self, name: &str) -> Result<ResponseEnvironment, vem_error_t> {
+        let environment = self.repository.get(name)?;
+
+        Ok(ResponseEnvironment {
+            mcode: "VEG1".to_string(),
+            messages: format!("Environment '{}' found", name),
+            environment,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Update an environment's description and tags
+    fn update(&self, request: RequestEnvironmentUpdate) -> Result<ResponseEnvironment, vem_error_t> {
+        let current = self.repository.get(&request.name)?;
+
+        // An empty --description clears it; no flag keeps the current one
+        let description = match request.description {
+            Some(description) if description.is_empty() => None,
+            Some(description) => Some(description),
+            None => current.description,
+        };
+
+        // Tag edits apply in order: clear, remove, then add
+        let mut tags = if request.clear_tags { Vec::new() } else { current.tags };
+        tags.retain(|tag| !request.remove_tags.contains(tag));
+        tags.extend(request.add_tags);
+
+        let backup = self.backups.snapshot(&request.name)?;
+        let environment = self.repository.update(&request.name, description, tags)?;
+
+        Ok(ResponseEnvironment {
+            mcode: "VEU1".to_string(),
+            messages: with_backup(format!("Environment '{}' updated", environment.name), backup.as_ref()),
+            environment,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Fail as `delete` would on a missing or in-use environment; both the global `current`
+    /// and this shell's VEM_ENV count as in use
+    fn check_delete(&self, name: &str) -> Result<(), vem_error_t> {
+        self.repository.get(name)?;
+        for current in [self.repository.get_linked(), self.repository.get_current()] {
+            if let Ok(current) = current
+                && current.name == name
+            {
+                return Err(vem_error_t::EnvironmentInUse(name.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Move an environment to the trash, backing it up first when backup_enabled is set or `backup` is requested
+    fn delete(&self, name: &str, backup: bool) -> Result<ResponseEnvironment, vem_error_t> {
+        // Refuse before snapshotting so a blocked delete leaves no stray backup
+        self.check_delete(name)?;
+        let backup = if backup { Some(self.backups.create(name)?) } else { self.backups.snapshot(name)? };
+        let environment = self.repository.delete(name)?;
+        self.activation.forget(name)?;
+
+        Ok(ResponseEnvironment {
+            mcode: "VED1".to_string(),
+            messages: with_backup(format!("Environment '{}' moved to trash", name), backup.as_ref()),
+            environment,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Bring an environment back from the trash
+    fn undelete(&self, name: &str) -> Result<ResponseEnvironment, vem_error_t> {
+        let environment = self.repository.undelete(name)?;
+
+        Ok(ResponseEnvironment {
+            mcode: "VER1".to_string(),
+            messages: format!("Environment '{}' restored from trash", name),
+            environment,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Get the current environment
+    fn get_current(&self) -> Result<ResponseEnvironment, vem_error_t> {
+        let (environment, source) = self.resolve_current()?;
+
+        let (mcode, messages) = match source {
+            current_source_t::SESSION => (
+                "VECU6",
+                format!("Using environment '{}' from VEM_ENV for this shell", environment.name),
+            ),
+            current_source_t::LOCAL(path) => (
+                "VECU7",
+                format!("Using environment '{}' from {}", environment.name, path.display()),
+            ),
+            current_source_t::LINK => ("VECU1", format!("Current environment is '{}'", environment.name)),
+            current_source_t::DEFAULT => (
+                "VECU4",
+                format!("No environment is active; using default environment '{}'", environment.name),
+            ),
+        };
+        Ok(ResponseEnvironment {
+            mcode: mcode.to_string(),
+            messages,
+            environment,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// With auto_switch set, switch to default_environment when nothing else names the current
+    /// environment; None when there was nothing to do
+    fn auto_switch(&self) -> Result<Option<ResponseEnvironment>, vem_error_t> {
+        if !self.config.auto_switch {
+            return Ok(None);
+        }
+        let Ok((default, current_source_t::DEFAULT)) = self.resolve_current() else {
+            return Ok(None);
+        };
+        let environment = self.activate(&default.name)?;
+
+        Ok(Some(ResponseEnvironment {
+            mcode: "VECU5".to_string(),
+            messages: format!("No environment was active; switched to default environment '{}'", environment.name),
+            warnings: self.activation.warnings(&environment.name)?,
+            environment,
+        }))
+    }
+
+    /// Switch the current environment
+    fn set_current(&self, name: &str) -> Result<ResponseEnvironment, vem_error_t> {
+        let environment = self.activate(name)?;
+
+        // The global switch does not reach shells pinned with `vem shell`
+        let messages = match session_environment() {
+            Some(session) if session != name => {
+                format!("Switched to environment '{}' (this shell stays on '{}' via VEM_ENV)", name, session)
+            },
+            _ => format!("Switched to environment '{}'", name),
+        };
+        Ok(ResponseEnvironment {
+            mcode: "VES1".to_string(),
+            messages,
+            environment,
+            warnings: self.activation.warnings(name)?,
+        })
+    }
+
+    /// Set or clear default_environment in config.toml; a name must exist
+    fn set_default(&self, name: Option<&str>) -> Result<ResponseEnvironmentDefault, vem_error_t> {
+        let environment = match name {
+            Some(name) => Some(self.repository.get(name)?),
+            None => None,
+        };
+
+        let mut config = self.config.clone();
+        config.default_environment = environment.as_ref().map(|env| env.name.clone());
+        config.save()?;
+
+        let messages = match &environment {
+            Some(environment) => format!("Default environment set to '{}'", environment.name),
+            None => "Default environment unset".to_string(),
+        };
+        Ok(ResponseEnvironmentDefault {
+            mcode: "VEDF1".to_string(),
+            messages,
+            environment,
+        })
+    }
+
+    /// Restore the user's own dotfiles and leave no environment active
+    fn deactivate(&self) -> Result<ResponseEnvironmentDeactivate, vem_error_t> {
+        let environment = match self.repository.get_linked() {
+            Ok(environment) => Some(environment),
+            Err(vem_error_t::NoCurrentEnvironment) | Err(vem_error_t::EnvironmentNotFound(_)) => None,
+            Err(err) => return Err(err),
+        };
+        let released = self.activation.deactivate(environment.as_ref().map(|environment| environment.name.as_str()))?;
+        self.repository.unset_current()?;
+
+        let messages = match &environment {
+            Some(environment) => format!("Environment '{}' deactivated", environment.name),
+            None => "No environment was active".to_string(),
+        };
+        Ok(ResponseEnvironmentDeactivate {
+            mcode: "VEDA1".to_string(),
+            messages,
+            environment,
+            released,
+        })
+    }
+
+    /// Prepare to run an editor in an environment without switching `current`;
+    /// with no name, the current environment is used, skipping VEM_ENV unless `session` is set.
+    /// `record_use` updates last_used.
+    fn launch(&self, name: Option<&str>, session: bool, record_use: bool) -> Result<ResponseEnvironmentLaunch, vem_error_t> {
+        let environment = match name {
+            Some(name) => self.repository.get(name)?,
+            None => self.resolve(session_environment().filter(|_| session))?.0,
+        };
+        let name = environment.name.clone();
+        let variables = self.activation.launch_variables(&name)?;
+        let environment = if record_use { self.repository.touch(&name)? } else { environment };
+
+        Ok(ResponseEnvironmentLaunch {
+            mcode: "VEX1".to_string(),
+            messages: format!("Launching in environment '{}'", name),
+            environment,
+            variables,
+            editor: self.config.editor.clone(),
+            warnings: self.activation.warnings(&name)?,
+        })
+    }
+
+    /// Find the .vem-env that applies to the working directory
+    fn get_local(&self) -> Result<ResponseEnvironmentLocal, vem_error_t> {
+        let cwd = std::env::current_dir()?;
+        let local = self.locals.find(&cwd)?;
+
+        let (mcode, messages) = match &local {
+            Some(local) => ("VELO4", format!("Local environment is '{}' ({})", local.environment, local.path.display())),
+            None => ("VELO3", format!("No .vem-env found in {} or its parents", cwd.display())),
+        };
+        Ok(ResponseEnvironmentLocal {
+            mcode: mcode.to_string(),
+            messages,
+            local,
+        })
+    }
+
+    /// Pin the working directory to an environment, or remove its .vem-env
+    fn set_local(&self, name: Option<&str>) -> Result<ResponseEnvironmentLocal, vem_error_t> {
+        let cwd = std::env::current_dir()?;
+
+        let Some(name) = name else {
+            let local = self.locals.remove(&cwd)?;
+            let messages = match &local {
+                Some(local) => format!("Removed {}", local.path.display()),
+                None => format!("No .vem-env in {}", cwd.display()),
+            };
+            return Ok(ResponseEnvironmentLocal {
+                mcode: "VELO2".to_string(),
+                messages,
+                local,
+            });
+        };
+
+        let environment = self.repository.get(name)?;
+        let local = self.locals.write(&cwd, &environment.name)?;
+        Ok(ResponseEnvironmentLocal {
+            mcode: "VELO1".to_string(),
+            messages: format!("Local environment set to '{}' ({})", environment.name, local.path.display()),
+            local: Some(local),
+        })
+    }
+
+    /// Report every source the current environment could come from, and which one won
+    fn explain_current(&self) -> Result<ResponseEnvironmentExplain, vem_error_t> {
+        let local = self.find_local()?;
+        let linked = match self.repository.get_linked() {
+            Ok(environment) => Some(environment.name),
+            Err(vem_error_t::EnvironmentNotFound(name)) => Some(name),
+            Err(vem_error_t::NoCurrentEnvironment) => None,
+            Err(err) => return Err(err),
+        };
+
+        let mut sources = vec![
+            CURRENT_SOURCE {
+                source: "VEM_ENV".to_string(),
+                environment: session_environment(),
+                detail: None,
+                selected: false,
+            },
+            CURRENT_SOURCE {
+                source: ".vem-env".to_string(),
+                environment: local.as_ref().map(|local| local.environment.clone()),
+                detail: local.as_ref().map(|local| local.path.display().to_string()),
+                selected: false,
+            },
+            CURRENT_SOURCE {
+                source: "current".to_string(),
+                environment: linked,
+                detail: Some(current_link_path().display().to_string()),
+                selected: false,
+            },
+            CURRENT_SOURCE {
+                source: "default_environment".to_string(),
+                environment: self.config.default_environment.clone(),
+                detail: None,
+                selected: false,
+            },
+        ];
+
+        // A source that names a missing environment is reported, not fatal
+        let (environment, messages) = match self.resolve_current() {
+            Ok((environment, source)) => {
+                let index = match source {
+                    current_source_t::SESSION => 0,
+                    current_source_t::LOCAL(_) => 1,
+                    current_source_t::LINK => 2,
+                    current_source_t::DEFAULT => 3,
+                };
+                sources[index].selected = true;
+                let messages = format!("Current environment is '{}' (from {})", environment.name, sources[index].source);
+                (Some(environment), messages)
+            },
+            Err(err) => (None, err.to_string()),
+        };
+
+        Ok(ResponseEnvironmentExplain {
+            mcode: "VECU8".to_string(),
+            messages,
+            environment,
+            sources,
+        })
+    }
+}
+
+// Append the automatic backup's id to a success message
+fn with_backup(message: String, backup: Option<&BACKUP>) -> String {
+    match backup {
+        Some(backup) => format!("{} (backup '{}')", message, backup.id),
+        None => message,
+    }
+}
+
+/// Factory function to create environment use case
+pub fn new(config: app_config) -> impl EnvironmentUsecase {
+    environment_usecase::new(
+        Box::new(environment_rep::new(config.clone())),
+        Box::new(template_rep::new()),
+        Box::new(backup_rep::new(config.clone())),
+        Box::new(activation_rep::new(config.clone())),
+        Box::new(local_rep::new()),
+        config,
+    )
+}
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+
+use crate::cnf::application::{app_config, session_environment, xdg_config_home};
+use crate::ent::response::shim::SHIM as ResponseShim;
+use crate::ent::response::shim::SHIMS as ResponseShims;
+use crate::rep::shim::{self as shim_rep, ShimRepository};
+use crate::usc::environment::{self as environment_usc, EnvironmentUsecase};
+use crate::util::error::vem_error_t;
+use crate::util::mcode;
+
+// Editors that get a shim
+const SHIM_PROGRAMS: [&str; 4] = ["vim", "nvim", "gvim", "view"];
+
+pub trait ShimUsecase {
+    fn install(&self) -> Result<ResponseShims, vem_error_t>;
+    fn resolve(&self, program: &str, record_use: bool) -> Result<ResponseShim, vem_error_t>;
+}
+
+/// Shim use case backed by the shim repository and environment resolution
+pub struct shim_usecase {
+    repository: Box<dyn ShimRepository>,
+    environments: Box<dyn EnvironmentUsecase>,
+}
+
+impl shim_usecase {
+    pub fn new(repository: Box<dyn ShimRepository>, environments: Box<dyn EnvironmentUsecase>) -> Self {
+        Self { repository, environments }
+    }
+}
+
+impl ShimUsecase for shim_usecase {
+    /// Install the vim/nvim/gvim/view shims
+    fn install(&self) -> Result<ResponseShims, vem_error_t> {
+        let vem = std::env::current_exe()?;
+        let shims = self.repository.install(&SHIM_PROGRAMS, &vem)?;
+        let bin = self.repository.bin_root().to_path_buf();
+
+        Ok(ResponseShims {
+            mcode: "VSI1".to_string(),
+            messages: format!(
+                "{}: {} shim(s) in {}; put it first on PATH (vem shell-init does this)",
+                mcode::VSI1,
+                shims.len(),
+                bin.display()
+            ),
+            bin,
+            shims,
+        })
+    }
+
+    /// Work out which editor binary a shim runs, in which environment and with which config
+    fn resolve(&self, program: &str, record_use: bool) -> Result<ResponseShim, vem_error_t> {
+        let binary = self.repository.find_real(program)?;
+
+        // A VEM_ENV left behind by a shell whose environment was since removed should not stop the
+        // editor; resolve as if it were unset (.vem-env, then `current`, then default_environment)
+        let mut warnings = Vec::new();
+        let mut session = true;
+        if let Some(name) = session_environment()
+            && let Err(vem_error_t::EnvironmentNotFound(_)) = self.environments.get(&name)
+        {
+            warnings.push(format!("VEM_ENV names environment '{}', which does not exist; ignoring it", name));
+            session = false;
+        }
+
+        // A shim must always start the editor: with no active environment, or one that cannot be
+        // resolved (a .vem-env or default_environment naming a removed environment, an unreadable
+        // meta.toml), it behaves like the plain editor
+        let (environment, mut variables) = match self.environments.launch(None, session, record_use) {
+            Ok(response) => (Some(response.environment.name), response.variables),
+            Err(vem_error_t::NoCurrentEnvironment) => (None, Vec::new()),
+            Err(err) => {
+                warnings.push(format!("{}; running {} without an environment", err, program));
+                (None, Vec::new())
+            },
+        };
+        // Nor should the editor, or a vem run from it, inherit the stale VEM_ENV
+        if !session && environment.is_none() {
+            variables.push(("VEM_ENV".to_string(), String::new()));
+        }
+        let config = loaded_config(program, &variables);
+
+        let (mcode, messages) = match &environment {
+            Some(environment) => (
+                "VSW1",
+                format!("{}: {} runs {} in environment '{}'", mcode::VSW1, program, binary.display(), environment),
+            ),
+            None => ("VSW3", format!("{}: {} runs {}", mcode::VSW3, program, binary.display())),
+        };
+        Ok(ResponseShim {
+            mcode: mcode.to_string(),
+            messages,
+            program: program.to_string(),
+            binary,
+            environment,
+            config,
+            variables,
+            warnings,
+        })
+    }
+}
+
+// The config file the editor will read given the launch variables; None means its usual default
+fn loaded_config(program: &str, variables: &[(String, String)]) -> Option<PathBuf> {
+    let variable = |key: &str| variables.iter().find(|(k, _)| k == key).map(|(_, value)| value.as_str());
+
+    // VIMINIT (which sources MYVIMRC) is honoured by Vim and Neovim alike
+    if let Some(vimrc) = variable("MYVIMRC") {
+        return Some(PathBuf::from(vimrc));
+    }
+    if program == "nvim"
+        && let Some(appname) = variable("NVIM_APPNAME")
+    {
+        return Some(xdg_config_home().join(appname).join("init.lua"));
+    }
+    None
+}
+
+/// Factory function to create shim use case
+pub fn new(config: app_config) -> impl ShimUsecase {
+    shim_usecase::new(Box::new(shim_rep::new()), Box::new(environment_usc::new(config)))
+}
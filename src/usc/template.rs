@@ -0,0 +1,71 @@
+use std::path::Path;
+
+use crate::ent::response::template::TEMPLATE as ResponseTemplate;
+use crate::ent::response::template::TEMPLATES as ResponseTemplates;
+use crate::rep::template::{self as template_rep, TemplateRepository};
+use crate::util::error::vem_error_t;
+use crate::util::mcode;
+
+pub trait TemplateUsecase {
+    fn list(&self) -> Result<ResponseTemplates, vem_error_t>;
+    fn get(&self, name: &str) -> Result<ResponseTemplate, vem_error_t>;
+    fn extract(&self, name: &str, dest: &Path) -> Result<ResponseTemplate, vem_error_t>;
+}
+
+/// Template use case backed by a template repository
+pub struct template_usecase {
+    repository: Box<dyn TemplateRepository>,
+}
+
+impl template_usecase {
+    pub fn new(repository: Box<dyn TemplateRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+impl TemplateUsecase for template_usecase {
+    /// List all available templates
+    fn list(&self) -> Result<ResponseTemplates, vem_error_t> {
+        let templates = self.repository.list()?;
+        if templates.is_empty() {
+            return Ok(ResponseTemplates {
+                mcode: "VTL3".to_string(),
+                messages: mcode::VTL3.to_string(),
+                templates,
+            });
+        }
+
+        Ok(ResponseTemplates {
+            mcode: "VTL1".to_string(),
+            messages: format!("{} template(s) found", templates.len()),
+            templates,
+        })
+    }
+
+    /// Get a template by name
+    fn get(&self, name: &str) -> Result<ResponseTemplate, vem_error_t> {
+        let template = self.repository.get(name)?;
+
+        Ok(ResponseTemplate {
+            mcode: "VTG1".to_string(),
+            messages: format!("Template '{}' found", name),
+            template,
+        })
+    }
+
+    /// Extract a template's files into a directory
+    fn extract(&self, name: &str, dest: &Path) -> Result<ResponseTemplate, vem_error_t> {
+        let template = self.repository.extract(name, dest)?;
+
+        Ok(ResponseTemplate {
+            mcode: "VTX1".to_string(),
+            messages: format!("Template '{}' extracted to {}", name, dest.display()),
+            template,
+        })
+    }
+}
+
+/// Factory function to create template use case
+pub fn new() -> impl TemplateUsecase {
+    template_usecase::new(Box::new(template_rep::new()))
+}
@@ -49,6 +49,15 @@ impl std::fmt::Debug for crate::util::error::vem_error_t {
             vem_error_t::EnvironmentNotFound(s) => f.debug_tuple("EnvironmentNotFound").field(s).finish(),
             vem_error_t::EnvironmentAlreadyExists(s) => f.debug_tuple("EnvironmentAlreadyExists").field(s).finish(),
             vem_error_t::InvalidEnvironmentName(s) => f.debug_tuple("InvalidEnvironmentName").field(s).finish(),
+            vem_error_t::EnvironmentInUse(s) => f.debug_tuple("EnvironmentInUse").field(s).finish(),
+            vem_error_t::TemplateNotFound(s) => f.debug_tuple("TemplateNotFound").field(s).finish(),
+            vem_error_t::BackupNotFound(s) => f.debug_tuple("BackupNotFound").field(s).finish(),
+            vem_error_t::CtagsRepositoryNotFound(s) => f.debug_tuple("CtagsRepositoryNotFound").field(s).finish(),
+            vem_error_t::TagSetNotFound(s) => f.debug_tuple("TagSetNotFound").field(s).finish(),
+            vem_error_t::TagFileNotFound(s) => f.debug_tuple("TagFileNotFound").field(s).finish(),
+            vem_error_t::CommandFailed(s) => f.debug_tuple("CommandFailed").field(s).finish(),
+            vem_error_t::InvalidTag(s) => f.debug_tuple("InvalidTag").field(s).finish(),
+            vem_error_t::InvalidArgument(s) => f.debug_tuple("InvalidArgument").field(s).finish(),
             vem_error_t::FileSystemError(e) => f.debug_tuple("FileSystemError").field(e).finish(),
             vem_error_t::ConfigurationError(s) => f.debug_tuple("ConfigurationError").field(s).finish(),
             vem_error_t::SerializationError(s) => f.debug_tuple("SerializationError").field(s).finish(),
@@ -80,7 +80,7 @@ impl<'de> Deserialize<'de> for crate::cnf::application::app_config {
                 }
 
                 Ok(crate::cnf::application::app_config {
-                    default_environment: default_environment.ok_or_else(|| de::Error::missing_field("default_environment"))?,
+                    default_environment: default_environment.unwrap_or_default(),
                     auto_switch: auto_switch.ok_or_else(|| de::Error::missing_field("auto_switch"))?,
                     backup_enabled: backup_enabled.ok_or_else(|| de::Error::missing_field("backup_enabled"))?,
                     backup_retention_days: backup_retention_days.ok_or_else(|| de::Error::missing_field("backup_retention_days"))?,
@@ -140,11 +140,11 @@ impl<'de> Deserialize<'de> for crate::ent::model::environment::ENVIRONMENT {
 
                 Ok(crate::ent::model::environment::ENVIRONMENT {
                     name: name.ok_or_else(|| de::Error::missing_field("name"))?,
-                    description: description.ok_or_else(|| de::Error::missing_field("description"))?,
+                    description: description.unwrap_or_default(),
                     created: created.ok_or_else(|| de::Error::missing_field("created"))?,
                     update: update.ok_or_else(|| de::Error::missing_field("update"))?,
-                    last_used: last_used.ok_or_else(|| de::Error::missing_field("last_used"))?,
-                    tags: tags.ok_or_else(|| de::Error::missing_field("tags"))?,
+                    last_used: last_used.unwrap_or_default(),
+                    tags: tags.unwrap_or_default(),
                 })
             }
         }
@@ -8,6 +8,24 @@ pub enum vem_error_t {
     EnvironmentAlreadyExists(String),
     /// Invalid environment name
     InvalidEnvironmentName(String),
+    /// Environment is currently active
+    EnvironmentInUse(String),
+    /// Template not found
+    TemplateNotFound(String),
+    /// Backup not found
+    BackupNotFound(String),
+    /// Ctags repository not defined in vem.toml
+    CtagsRepositoryNotFound(String),
+    /// Ctags tag set not defined in vem.toml
+    TagSetNotFound(String),
+    /// Tag file not found
+    TagFileNotFound(String),
+    /// External command failed
+    CommandFailed(String),
+    /// Invalid environment tag
+    InvalidTag(String),
+    /// Invalid command-line argument
+    InvalidArgument(String),
     /// File system error
     FileSystemError(std::io::Error),
     /// Configuration error
@@ -30,6 +48,33 @@ impl fmt::Display for vem_error_t {
             vem_error_t::InvalidEnvironmentName(name) => {
                 write!(f, "Invalid environment name: '{}'", name)
             },
+            vem_error_t::EnvironmentInUse(name) => {
+                write!(f, "Environment '{}' is currently active", name)
+            },
+            vem_error_t::TemplateNotFound(name) => {
+                write!(f, "Template '{}' not found", name)
+            },
+            vem_error_t::BackupNotFound(name) => {
+                write!(f, "Backup '{}' not found", name)
+            },
+            vem_error_t::CtagsRepositoryNotFound(name) => {
+                write!(f, "Repository '{}' not found in configuration", name)
+            },
+            vem_error_t::TagSetNotFound(name) => {
+                write!(f, "Tag set '{}' not found in configuration", name)
+            },
+            vem_error_t::TagFileNotFound(name) => {
+                write!(f, "Tag file '{}' not found", name)
+            },
+            vem_error_t::CommandFailed(msg) => {
+                write!(f, "Command failed: {}", msg)
+            },
+            vem_error_t::InvalidTag(tag) => {
+                write!(f, "Invalid tag: '{}'", tag)
+            },
+            vem_error_t::InvalidArgument(msg) => {
+                write!(f, "Invalid argument: {}", msg)
+            },
             vem_error_t::FileSystemError(err) => {
                 write!(f, "File system error: {}", err)
             },
@@ -69,16 +114,12 @@ pub enum exit_code_t {
     INVALID_ARGUMENTS = 2,
     ENVIRONMENT_NOT_FOUND = 3,
     ENVIRONMENT_ALREADY_EXISTS = 4,
+    PERMISSION_DENIED = 5,
 }
 
 impl From<vem_error_t> for exit_code_t {
     fn from(err: vem_error_t) -> Self {
-        match err {
-            vem_error_t::EnvironmentNotFound(_) => exit_code_t::ENVIRONMENT_NOT_FOUND,
-            vem_error_t::EnvironmentAlreadyExists(_) => exit_code_t::ENVIRONMENT_ALREADY_EXISTS,
-            vem_error_t::InvalidEnvironmentName(_) => exit_code_t::INVALID_ARGUMENTS,
-            _ => exit_code_t::GENERAL_ERROR,
-        }
+        exit_code_t::from(&err)
     }
 }
 
@@ -88,6 +129,16 @@ impl From<&vem_error_t> for exit_code_t {
             vem_error_t::EnvironmentNotFound(_) => exit_code_t::ENVIRONMENT_NOT_FOUND,
             vem_error_t::EnvironmentAlreadyExists(_) => exit_code_t::ENVIRONMENT_ALREADY_EXISTS,
             vem_error_t::InvalidEnvironmentName(_) => exit_code_t::INVALID_ARGUMENTS,
+            vem_error_t::TemplateNotFound(_) => exit_code_t::ENVIRONMENT_NOT_FOUND,
+            vem_error_t::BackupNotFound(_) => exit_code_t::ENVIRONMENT_NOT_FOUND,
+            vem_error_t::CtagsRepositoryNotFound(_) => exit_code_t::ENVIRONMENT_NOT_FOUND,
+            vem_error_t::TagSetNotFound(_) => exit_code_t::ENVIRONMENT_NOT_FOUND,
+            vem_error_t::TagFileNotFound(_) => exit_code_t::ENVIRONMENT_NOT_FOUND,
+            vem_error_t::InvalidTag(_) => exit_code_t::INVALID_ARGUMENTS,
+            vem_error_t::InvalidArgument(_) => exit_code_t::INVALID_ARGUMENTS,
+            vem_error_t::FileSystemError(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                exit_code_t::PERMISSION_DENIED
+            },
             _ => exit_code_t::GENERAL_ERROR,
         }
     }
@@ -94,10 +94,12 @@ impl vem_env_list {
     const VEL1: &'static str = "Environment list success";
     const VEL2: &'static str = "Environment list failed";
     const VEL3: &'static str = "No environments found";
+    const VEL4: &'static str = "Environment skipped in list";
 }
 pub const VEL1: &str = vem_env_list::VEL1;
 pub const VEL2: &str = vem_env_list::VEL2;
 pub const VEL3: &str = vem_env_list::VEL3;
+pub const VEL4: &str = vem_env_list::VEL4;
 
 // VEM_ENV_GET - Environment retrieval
 struct vem_env_get;
@@ -113,8 +115,8 @@ pub const VEG3: &str = vem_env_get::VEG3;
 // VEM_ENV_UPDATE - Environment update
 struct vem_env_update;
 impl vem_env_update {
-    const VEU1: &'static str = " Environment update success";
-    const VEU2: &str = "Environment update failed";
+    const VEU1: &'static str = "Environment update success";
+    const VEU2: &'static str = "Environment update failed";
 }
 pub const VEU1: &str = vem_env_update::VEU1;
 pub const VEU2: &str = vem_env_update::VEU2;
@@ -130,6 +132,15 @@ pub const VED1: &str = vem_env_delete::VED1;
 pub const VED2: &str = vem_env_delete::VED2;
 pub const VED3: &str = vem_env_delete::VED3;
 
+// VEM_ENV_UNDELETE - Environment restore from trash
+struct vem_env_undelete;
+impl vem_env_undelete {
+    const VER1: &'static str = "Environment undelete success";
+    const VER2: &'static str = "Environment not found in trash";
+}
+pub const VER1: &str = vem_env_undelete::VER1;
+pub const VER2: &str = vem_env_undelete::VER2;
+
 // VEM_ENV_SWITCH - Environment switching
 struct vem_env_switch;
 impl vem_env_switch {
@@ -139,16 +150,219 @@ impl vem_env_switch {
 pub const VES1: &str = vem_env_switch::VES1;
 pub const VES2: &str = vem_env_switch::VES2;
 
+// VEM_ENV_DEACTIVATE - Environment deactivation
+struct vem_env_deactivate;
+impl vem_env_deactivate {
+    const VEDA1: &'static str = "Environment deactivate success";
+    const VEDA2: &'static str = "Environment deactivate failed";
+}
+pub const VEDA1: &str = vem_env_deactivate::VEDA1;
+pub const VEDA2: &str = vem_env_deactivate::VEDA2;
+
+// VEM_ENV_EXEC - Launching an editor inside an environment
+struct vem_env_exec;
+impl vem_env_exec {
+    const VEX1: &'static str = "Environment launch success";
+    const VEX2: &'static str = "Environment launch failed";
+}
+pub const VEX1: &str = vem_env_exec::VEX1;
+pub const VEX2: &str = vem_env_exec::VEX2;
+
 // VEM_ENV_CURRENT - Current environment operations
 struct vem_env_current;
 impl vem_env_current {
     const VECU1: &'static str = "Get current environment success";
     const VECU2: &'static str = "No current environment set";
     const VECU3: &'static str = "Get current environment failed";
+    const VECU4: &'static str = "Using default environment";
+    const VECU5: &'static str = "Auto-switched to default environment";
+    const VECU6: &'static str = "Using session environment (VEM_ENV)";
+    const VECU7: &'static str = "Using local environment (.vem-env)";
+    const VECU8: &'static str = "Explain current environment success";
 }
 pub const VECU1: &str = vem_env_current::VECU1;
 pub const VECU2: &str = vem_env_current::VECU2;
 pub const VECU3: &str = vem_env_current::VECU3;
+pub const VECU4: &str = vem_env_current::VECU4;
+pub const VECU5: &str = vem_env_current::VECU5;
+pub const VECU6: &str = vem_env_current::VECU6;
+pub const VECU7: &str = vem_env_current::VECU7;
+pub const VECU8: &str = vem_env_current::VECU8;
+
+// VEM_ENV_LOCAL - Per-directory environments (.vem-env)
+struct vem_env_local;
+impl vem_env_local {
+    const VELO1: &'static str = "Local environment set";
+    const VELO2: &'static str = "Local environment unset";
+    const VELO3: &'static str = "No local environment";
+    const VELO4: &'static str = "Local environment found";
+}
+pub const VELO1: &str = vem_env_local::VELO1;
+pub const VELO2: &str = vem_env_local::VELO2;
+pub const VELO3: &str = vem_env_local::VELO3;
+pub const VELO4: &str = vem_env_local::VELO4;
+
+// VEM_ENV_DEFAULT - Default environment
+struct vem_env_default;
+impl vem_env_default {
+    const VEDF1: &'static str = "Default environment update success";
+    const VEDF2: &'static str = "Default environment update failed";
+}
+pub const VEDF1: &str = vem_env_default::VEDF1;
+pub const VEDF2: &str = vem_env_default::VEDF2;
+
+/* Shim Operations - VEM_SHIM_*/
+// VEM_SHIM_INSTALL - Editor shim installation
+struct vem_shim_install;
+impl vem_shim_install {
+    const VSI1: &'static str = "Shim install success";
+    const VSI2: &'static str = "Shim install failed";
+}
+pub const VSI1: &str = vem_shim_install::VSI1;
+pub const VSI2: &str = vem_shim_install::VSI2;
+
+// VEM_SHIM_WHICH - Editor shim resolution
+struct vem_shim_which;
+impl vem_shim_which {
+    const VSW1: &'static str = "Shim resolve success";
+    const VSW2: &'static str = "Shim resolve failed";
+    const VSW3: &'static str = "No vem environment for the shim";
+    const VSW4: &'static str = "Shim environment not resolved";
+}
+pub const VSW1: &str = vem_shim_which::VSW1;
+pub const VSW2: &str = vem_shim_which::VSW2;
+pub const VSW3: &str = vem_shim_which::VSW3;
+pub const VSW4: &str = vem_shim_which::VSW4;
+
+/* Template Operations - VEM_TEMPLATE_*/
+// VEM_TEMPLATE_LIST - Template listing
+struct vem_template_list;
+impl vem_template_list {
+    const VTL1: &'static str = "Template list success";
+    const VTL2: &'static str = "Template list failed";
+    const VTL3: &'static str = "No templates found";
+}
+pub const VTL1: &str = vem_template_list::VTL1;
+pub const VTL2: &str = vem_template_list::VTL2;
+pub const VTL3: &str = vem_template_list::VTL3;
+
+// VEM_TEMPLATE_GET - Template retrieval
+struct vem_template_get;
+impl vem_template_get {
+    const VTG1: &'static str = "Template get success";
+    const VTG2: &'static str = "Template not found";
+}
+pub const VTG1: &str = vem_template_get::VTG1;
+pub const VTG2: &str = vem_template_get::VTG2;
+
+// VEM_TEMPLATE_EXTRACT - Template extraction
+struct vem_template_extract;
+impl vem_template_extract {
+    const VTX1: &'static str = "Template extract success";
+    const VTX2: &'static str = "Template extract failed";
+}
+pub const VTX1: &str = vem_template_extract::VTX1;
+pub const VTX2: &str = vem_template_extract::VTX2;
+
+/* Backup Operations - VEM_BACKUP_*/
+// VEM_BACKUP_CREATE - Backup creation
+struct vem_backup_create;
+impl vem_backup_create {
+    const VBC1: &'static str = "Backup create success";
+    const VBC2: &'static str = "Backup create failed";
+}
+pub const VBC1: &str = vem_backup_create::VBC1;
+pub const VBC2: &str = vem_backup_create::VBC2;
+
+// VEM_BACKUP_LIST - Backup listing
+struct vem_backup_list;
+impl vem_backup_list {
+    const VBL1: &'static str = "Backup list success";
+    const VBL2: &'static str = "Backup list failed";
+    const VBL3: &'static str = "No backups found";
+}
+pub const VBL1: &str = vem_backup_list::VBL1;
+pub const VBL2: &str = vem_backup_list::VBL2;
+pub const VBL3: &str = vem_backup_list::VBL3;
+
+// VEM_BACKUP_RESTORE - Backup restoration
+struct vem_backup_restore;
+impl vem_backup_restore {
+    const VBR1: &'static str = "Backup restore success";
+    const VBR2: &'static str = "Backup restore failed";
+}
+pub const VBR1: &str = vem_backup_restore::VBR1;
+pub const VBR2: &str = vem_backup_restore::VBR2;
+
+// VEM_BACKUP_PRUNE - Backup retention
+struct vem_backup_prune;
+impl vem_backup_prune {
+    const VBP1: &'static str = "Backup prune success";
+    const VBP2: &'static str = "Backup prune failed";
+}
+pub const VBP1: &str = vem_backup_prune::VBP1;
+pub const VBP2: &str = vem_backup_prune::VBP2;
+
+/* Ctags Operations - VEM_CTAGS_*/
+// VEM_CTAGS_GENERATE - Tag file generation
+struct vem_ctags_generate;
+impl vem_ctags_generate {
+    const VCTG1: &'static str = "Ctags generate success";
+    const VCTG2: &'static str = "Ctags generate failed";
+}
+pub const VCTG1: &str = vem_ctags_generate::VCTG1;
+pub const VCTG2: &str = vem_ctags_generate::VCTG2;
+
+// VEM_CTAGS_UPDATE - Tag file update
+struct vem_ctags_update;
+impl vem_ctags_update {
+    const VCTU1: &'static str = "Ctags update success";
+    const VCTU2: &'static str = "Ctags update failed";
+    const VCTU3: &'static str = "No enabled repositories to update";
+}
+pub const VCTU1: &str = vem_ctags_update::VCTU1;
+pub const VCTU2: &str = vem_ctags_update::VCTU2;
+pub const VCTU3: &str = vem_ctags_update::VCTU3;
+
+// VEM_CTAGS_DELETE - Tag file deletion
+struct vem_ctags_delete;
+impl vem_ctags_delete {
+    const VCTD1: &'static str = "Ctags delete success";
+    const VCTD2: &'static str = "Ctags delete failed";
+}
+pub const VCTD1: &str = vem_ctags_delete::VCTD1;
+pub const VCTD2: &str = vem_ctags_delete::VCTD2;
+
+// VEM_CTAGS_LIST - Tag file listing
+struct vem_ctags_list;
+impl vem_ctags_list {
+    const VCTL1: &'static str = "Ctags list success";
+    const VCTL2: &'static str = "Ctags list failed";
+    const VCTL3: &'static str = "No tag files found";
+}
+pub const VCTL1: &str = vem_ctags_list::VCTL1;
+pub const VCTL2: &str = vem_ctags_list::VCTL2;
+pub const VCTL3: &str = vem_ctags_list::VCTL3;
+
+// VEM_CTAGS_CLEAN - Tag directory cleanup
+struct vem_ctags_clean;
+impl vem_ctags_clean {
+    const VCTC1: &'static str = "Ctags clean success";
+    const VCTC2: &'static str = "Ctags clean failed";
+}
+pub const VCTC1: &str = vem_ctags_clean::VCTC1;
+pub const VCTC2: &str = vem_ctags_clean::VCTC2;
+
+// VEM_CTAGS_WATCH - Tag updates on file changes
+struct vem_ctags_watch;
+impl vem_ctags_watch {
+    const VCTW1: &'static str = "Ctags watch started";
+    const VCTW2: &'static str = "Ctags watch failed";
+    const VCTW3: &'static str = "Ctags watch update";
+}
+pub const VCTW1: &str = vem_ctags_watch::VCTW1;
+pub const VCTW2: &str = vem_ctags_watch::VCTW2;
+pub const VCTW3: &str = vem_ctags_watch::VCTW3;
 
 /* Metadata Operations - VEM_META_*/
 // VEM_META_SAVE - Metadata saving
@@ -173,6 +387,14 @@ pub const VML1: &str = vem_meta_load::VML1;
 pub const VML2: &str = vem_meta_load::VML2;
 pub const VML3: &str = vem_meta_load::VML3;
 
+/* Manifest Operations - VEM_MANIFEST_*/
+// VEM_MANIFEST_LOAD - vem.toml loading
+struct vem_manifest_load;
+impl vem_manifest_load {
+    const VMF1: &'static str = "Manifest has unknown keys";
+}
+pub const VMF1: &str = vem_manifest_load::VMF1;
+
 /* Use-case (USC) Layer Codes - VEM_USC_*/
 // VEM_USC_INIT - Use-case initialization
 struct vem_usc_init;
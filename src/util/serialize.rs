@@ -53,3 +53,280 @@ impl serde::Serialize for crate::ent::model::environment::ENVIRONMENT {
         state.end()
     }
 }
+
+// Serialization for editor_type_t
+impl serde::Serialize for crate::ent::model::manifest::editor_type_t {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use crate::ent::model::manifest::editor_type_t;
+        match self {
+            editor_type_t::VIM => serializer.serialize_str("vim"),
+            editor_type_t::NEOVIM => serializer.serialize_str("neovim"),
+        }
+    }
+}
+
+// Serialization for TEMPLATE (manifest summary and file list, not file contents)
+impl serde::Serialize for crate::ent::model::template::TEMPLATE {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let files: Vec<String> = self.files.iter().map(|file| file.path.display().to_string()).collect();
+        let mut state = serializer.serialize_struct("TEMPLATE", 9)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("origin", &self.origin)?;
+        state.serialize_field("description", &self.manifest.environment.description)?;
+        state.serialize_field("version", &self.manifest.environment.version)?;
+        state.serialize_field("author", &self.manifest.environment.author)?;
+        state.serialize_field("editor_type", &self.manifest.editor.editor_type)?;
+        state.serialize_field("config_file", &self.manifest.editor.config_file)?;
+        state.serialize_field("tags", &self.manifest.environment.tags)?;
+        state.serialize_field("files", &files)?;
+        state.end()
+    }
+}
+
+// Serialization for response ENVIRONMENT
+impl serde::Serialize for crate::ent::response::environment::ENVIRONMENT {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ENVIRONMENT", 3)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("environment", &self.environment)?;
+        state.end()
+    }
+}
+
+// Serialization for response ENVIRONMENTS
+impl serde::Serialize for crate::ent::response::environment::ENVIRONMENTS {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ENVIRONMENTS", 5)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("current", &self.current)?;
+        state.serialize_field("environments", &self.environments)?;
+        state.serialize_field("sizes", &self.sizes)?;
+        state.end()
+    }
+}
+
+// Serialization for response CURRENT_SOURCE
+impl serde::Serialize for crate::ent::response::environment::CURRENT_SOURCE {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("CURRENT_SOURCE", 4)?;
+        state.serialize_field("source", &self.source)?;
+        state.serialize_field("environment", &self.environment)?;
+        state.serialize_field("detail", &self.detail)?;
+        state.serialize_field("selected", &self.selected)?;
+        state.end()
+    }
+}
+
+// Serialization for response ENVIRONMENT_EXPLAIN
+impl serde::Serialize for crate::ent::response::environment::ENVIRONMENT_EXPLAIN {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ENVIRONMENT_EXPLAIN", 4)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("environment", &self.environment)?;
+        state.serialize_field("sources", &self.sources)?;
+        state.end()
+    }
+}
+
+// Serialization for LOCAL
+impl serde::Serialize for crate::ent::model::local::LOCAL {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("LOCAL", 2)?;
+        state.serialize_field("environment", &self.environment)?;
+        state.serialize_field("path", &self.path)?;
+        state.end()
+    }
+}
+
+// Serialization for response ENVIRONMENT_LOCAL
+impl serde::Serialize for crate::ent::response::environment::ENVIRONMENT_LOCAL {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ENVIRONMENT_LOCAL", 3)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("local", &self.local)?;
+        state.end()
+    }
+}
+
+// Serialization for response TEMPLATE
+impl serde::Serialize for crate::ent::response::template::TEMPLATE {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TEMPLATE", 3)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("template", &self.template)?;
+        state.end()
+    }
+}
+
+// Serialization for response TEMPLATES
+impl serde::Serialize for crate::ent::response::template::TEMPLATES {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TEMPLATES", 3)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("templates", &self.templates)?;
+        state.end()
+    }
+}
+
+// Serialization for BACKUP
+impl serde::Serialize for crate::ent::model::backup::BACKUP {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("BACKUP", 5)?;
+        state.serialize_field("environment", &self.environment)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("created", &self.created)?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("size", &self.size)?;
+        state.end()
+    }
+}
+
+// Serialization for response BACKUP
+impl serde::Serialize for crate::ent::response::backup::BACKUP {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("BACKUP", 3)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("backup", &self.backup)?;
+        state.end()
+    }
+}
+
+// Serialization for response BACKUPS
+impl serde::Serialize for crate::ent::response::backup::BACKUPS {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("BACKUPS", 3)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("backups", &self.backups)?;
+        state.end()
+    }
+}
+
+// Serialization for ctag_status_t
+impl serde::Serialize for crate::ent::model::ctags::ctag_status_t {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use crate::ent::model::ctags::ctag_status_t;
+        match self {
+            ctag_status_t::ACTIVE => serializer.serialize_str("active"),
+            ctag_status_t::DISABLED => serializer.serialize_str("disabled"),
+            ctag_status_t::ORPHANED => serializer.serialize_str("orphaned"),
+        }
+    }
+}
+
+// Serialization for CTAG
+impl serde::Serialize for crate::ent::model::ctags::CTAG {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("CTAG", 6)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("repository", &self.repository)?;
+        state.serialize_field("tag_file", &self.tag_file)?;
+        state.serialize_field("last_updated", &self.last_updated)?;
+        state.serialize_field("size", &self.size)?;
+        state.serialize_field("status", &self.status)?;
+        state.end()
+    }
+}
+
+// Serialization for response CTAGS
+impl serde::Serialize for crate::ent::response::ctags::CTAGS {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("CTAGS", 5)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("environment", &self.environment)?;
+        state.serialize_field("tags", &self.tags)?;
+        state.serialize_field("fields", &self.fields)?;
+        state.end()
+    }
+}
+
+// Serialization for response SHIM; variables become a table keyed by name
+impl serde::Serialize for crate::ent::response::shim::SHIM {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let variables: std::collections::BTreeMap<&str, &str> =
+            self.variables.iter().map(|(key, value)| (key.as_str(), value.as_str())).collect();
+        let mut state = serializer.serialize_struct("SHIM", 7)?;
+        state.serialize_field("mcode", &self.mcode)?;
+        state.serialize_field("messages", &self.messages)?;
+        state.serialize_field("program", &self.program)?;
+        state.serialize_field("binary", &self.binary)?;
+        state.serialize_field("environment", &self.environment)?;
+        state.serialize_field("config", &self.config)?;
+        state.serialize_field("variables", &variables)?;
+        state.end()
+    }
+}
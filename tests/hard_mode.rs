@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// A throwaway HOME and VEM_HOME with symlink_mode = "hard"
+fn sandbox(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("vem-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("home")).unwrap();
+    fs::create_dir_all(root.join("vem")).unwrap();
+    fs::write(
+        root.join("vem").join("config.toml"),
+        format!(
+            concat!(
+                "auto_switch = false\n",
+                "backup_enabled = false\n",
+                "backup_retention_days = 30\n",
+                "environment_root = \"{}\"\n",
+                "symlink_mode = \"hard\"\n",
+                "editor = \"vim\"\n",
+            ),
+            root.join("vem").join("environments").display()
+        ),
+    )
+    .unwrap();
+    root
+}
+
+fn vem(root: &Path, args: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_vem"))
+        .args(args)
+        .env("HOME", root.join("home"))
+        .env("VEM_HOME", root.join("vem"))
+        .env("XDG_CONFIG_HOME", root.join("home").join(".config"))
+        .env_remove("VEM_ENV")
+        .env_remove("VEM_CONFIG")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "vem {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn switching_keeps_files_saved_into_the_mirror() {
+    let root = sandbox("hard-switch");
+    vem(&root, &["create", "environment", "a"]);
+    vem(&root, &["create", "environment", "b"]);
+    vem(&root, &["switch", "environment", "a"]);
+
+    // What editors do under ~/.vim: new files and directories, and files replaced rather than edited
+    let current = root.join("vem").join("current");
+    fs::create_dir_all(current.join(".vim").join("spell")).unwrap();
+    fs::write(current.join(".vim").join("spell").join("en.utf-8.add"), "vem\n").unwrap();
+    fs::write(current.join("meta.toml.new"), "new\n").unwrap();
+    let saved = fs::read_to_string(current.join("meta.toml")).unwrap() + "# edited\n";
+    fs::remove_file(current.join("meta.toml")).unwrap();
+    fs::write(current.join("meta.toml"), &saved).unwrap();
+
+    vem(&root, &["switch", "environment", "b"]);
+
+    let env = root.join("vem").join("environments").join("a");
+    assert_eq!(fs::read_to_string(env.join(".vim").join("spell").join("en.utf-8.add")).unwrap(), "vem\n");
+    assert_eq!(fs::read_to_string(env.join("meta.toml.new")).unwrap(), "new\n");
+    assert!(fs::read_to_string(env.join("meta.toml")).unwrap().contains("# edited"));
+
+    fs::remove_dir_all(&root).unwrap();
+}